@@ -0,0 +1,22 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serde_json::Value;
+
+use aoc22::day13::Element;
+
+// Whatever parses as a packet prints back as the same text and survives a trip through JSON
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(element) = text.parse::<Element>() else {
+        return;
+    };
+
+    assert_eq!(element.to_string(), text);
+
+    let value = Value::from(&element);
+    assert_eq!(Element::try_from(&value), Ok(element));
+    assert_eq!(serde_json::from_str::<Value>(text).ok(), Some(value));
+});
@@ -0,0 +1,121 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc22::shared::runner::{self, Part, DAYS};
+
+const USAGE: &str = "\
+Usage:
+    aoc run <day> <a|b> [--input <path>]    Solve one part, reading stdin unless --input is given
+    aoc run all [--inputs <dir>]            Solve every day, reading <dir>/dayNN.txt (default .inputs)";
+
+fn option_value(args: &[String], name: &str) -> Result<Option<String>, String> {
+    let mut value = None;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        if arg == name {
+            let v = rest.next().ok_or(format!("Missing value for {name}"))?;
+            value = Some(v.clone());
+        } else {
+            return Err(format!("Unexpected argument '{arg}'"));
+        }
+    }
+
+    Ok(value)
+}
+
+fn run_one(day: u32, part: Part, input_path: Option<PathBuf>) -> Result<(), String> {
+    let solution = runner::find_day(day)
+        .ok_or(format!("Day {day} is not solved"))?
+        .solve;
+
+    let input = match input_path {
+        Some(path) => std::fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read {}: {e}", path.display()))?,
+        None => aoc22::shared::read_stdin(),
+    };
+
+    match runner::run_timed(solution, part, &input) {
+        (Ok(Some(answer)), _) => {
+            println!("{answer}");
+            Ok(())
+        }
+        (Ok(None), _) => Err(format!("Day {day} part {part} has no answer")),
+        (Err(e), _) => Err(e.to_string()),
+    }
+}
+
+fn print_row(day: &str, part: &str, answer: &str, time: &str) {
+    let mut answer_lines = answer.lines();
+    let first = answer_lines.next().unwrap_or("");
+    println!("{day:>3}  {part:>4}  {first:<40}  {time:>12}");
+    for line in answer_lines {
+        println!("{:>3}  {:>4}  {line}", "", "");
+    }
+}
+
+fn run_all(inputs: &Path) -> Result<(), String> {
+    print_row("Day", "Part", "Answer", "Time");
+
+    let mut total = Duration::ZERO;
+    for day in DAYS {
+        let path = inputs.join(format!("day{:02}.txt", day.number));
+        let input = std::fs::read_to_string(&path);
+
+        for part in [Part::A, Part::B] {
+            let day_label = day.number.to_string();
+            let part_label = part.to_string();
+            let input = match &input {
+                Ok(input) => input,
+                Err(_) => {
+                    let missing = format!("missing {}", path.display());
+                    print_row(&day_label, &part_label, &missing, "-");
+                    continue;
+                }
+            };
+
+            let (answer, elapsed) = runner::run_timed(day.solve, part, input);
+            total += elapsed;
+            let answer = match answer {
+                Ok(Some(answer)) => answer,
+                Ok(None) => "no answer".to_string(),
+                Err(e) => e.to_string(),
+            };
+            print_row(&day_label, &part_label, &answer, &format!("{elapsed:.2?}"));
+        }
+    }
+
+    print_row("", "", "Total", &format!("{total:.2?}"));
+
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    match args {
+        [command, which, rest @ ..] if command == "run" && which == "all" => {
+            let inputs = option_value(rest, "--inputs")?.unwrap_or(".inputs".to_string());
+            run_all(Path::new(&inputs))
+        }
+        [command, day, part, rest @ ..] if command == "run" => {
+            let day = day
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid day '{day}'"))?;
+            let part = part.parse::<Part>()?;
+            let input_path = option_value(rest, "--input")?.map(PathBuf::from);
+            run_one(day, part, input_path)
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,24 +1,8 @@
-use itertools::Itertools;
+use std::process::ExitCode;
 
-fn main() {
-    let maybe_lines: Result<Vec<String>, _> = std::io::stdin().lines().collect();
-    let lines = maybe_lines.unwrap();
+use aoc22::day01::Day01;
+use aoc22::shared::runner::{run_stdin, Part};
 
-    let max = lines.into_iter()
-                   .group_by(|l| l.is_empty())
-                   .into_iter()
-                   .filter_map(
-                   |(empty, g)| {
-                       if empty {
-                           None
-                       }
-                       else
-                       {
-                           let items: Result<Vec<i32>, _> = g.map(|l| l.trim().parse::<i32>())
-                                                               .collect();
-                           Some(items.unwrap().iter().sum::<i32>())
-                       }
-                   }).max().unwrap();
-
-    println!("{max}");
+fn main() -> ExitCode {
+    run_stdin::<Day01>(Part::A)
 }
@@ -1,34 +1,8 @@
-use itertools::Itertools;
+use std::process::ExitCode;
 
-type Assignment = (i32, i32);
+use aoc22::day04::Day04;
+use aoc22::shared::runner::{run_stdin, Part};
 
-fn parse_assignment(s: &str) -> Assignment {
-    let (sp1, sp2) = s.split('-').collect_tuple().unwrap();
-
-    (sp1.parse::<i32>().unwrap(), sp2.parse::<i32>().unwrap())
-}
-
-fn do_overlap(some: &Assignment, other: &Assignment) -> bool {
-    let (l1, u1) = some;
-    let (l2, u2) = other;
-
-    u1 >= l2 && l1 <= u2
+fn main() -> ExitCode {
+    run_stdin::<Day04>(Part::B)
 }
-
-fn main() {
-    let result = std::io::stdin()
-        .lines()
-        .map(|maybe_line| maybe_line.unwrap())
-        .filter(
-            |line| {
-                let (p1, p2) = line.split(',').collect_tuple().unwrap();
-                let a1 = parse_assignment(p1);
-                let a2 = parse_assignment(p2);
-
-                do_overlap(&a1, &a2)
-            }
-        )
-        .count();
-
-    println!("{result}");
-}
\ No newline at end of file
@@ -1,15 +1,8 @@
-use std::collections::HashSet;
+use std::process::ExitCode;
 
-fn main() {
-    let mut line = String::new();
-    std::io::stdin().read_line(&mut line).unwrap();
+use aoc22::day06::{self, Day06};
+use aoc22::shared::runner::{run_cli, Part};
 
-    let cs: Vec<char> = line.chars().collect();
-    for pos in 4..cs.len() {
-        let set: HashSet<char> = HashSet::from_iter(cs[(pos - 4)..pos].iter().cloned());
-        if set.len() == 4 {
-            println!("{pos}");
-            break;
-        }
-    }
+fn main() -> ExitCode {
+    run_cli::<Day06>(Part::A, &day06::CLI)
 }
@@ -1,99 +1,36 @@
-use core::slice::Iter;
-use std::iter::Peekable;
-use itertools::Itertools;
-
-#[derive(Debug)]
-struct File {
-    size: usize,
-    #[allow(dead_code)]
-    name: String
-}
-
-#[derive(Debug)]
-struct Directory {
-    #[allow(dead_code)]
-    name: String,
-    files: Vec<File>,
-    subdirs: Vec<Directory>
-}
-
-impl Directory {
-    fn files_size(&self) -> usize {
-        self.files.iter().map(|f| f.size).sum::<usize>()
-    }
-}
-
-fn parse_dirname_from_cd(s: &str) -> String {
-    let (_, cmd, dir_name) = s
-        .split_ascii_whitespace()
-        .collect_tuple()
-        .expect("Line can be collected to 3-element tuple");
-
-    if cmd != "cd" {
-        panic!("Expected cd command");
-    }
-
-    dir_name.to_string()
-}
-
-fn accumulate_total_size_of_small_directories(result: &mut usize,  dir: &Directory) -> usize {
-    let mut this_dir_size = 0usize;
-    if !dir.subdirs.is_empty() {
-        dir.subdirs.iter().for_each(
-            |sd| { this_dir_size += accumulate_total_size_of_small_directories(result, sd); }
-        )
+use std::error::Error;
+use std::process::ExitCode;
+
+use aoc22::day07::{self, Day07};
+use aoc22::shared::cli::Options;
+use aoc22::shared::runner::{exit_code, parse_stdin, run_stdin, Part};
+
+/// `--limit <bytes>` changes which directories count as small, `--tree` and `--du` print the
+/// directory tree below the answer.
+fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    let fs = parse_stdin::<Day07>()?;
+    let sizes = fs.sizes();
+
+    let limit = options
+        .parsed::<usize>("--limit")?
+        .unwrap_or(day07::SMALL_DIRECTORY);
+    println!("{}", day07::sum_of_small_directories(&fs, &sizes, limit));
+
+    if options.flag("--tree") {
+        println!("{}", day07::tree(&fs, &sizes));
     }
 
-    this_dir_size += dir.files_size();
-    if this_dir_size <= 100_000usize {
-        (*result) += this_dir_size;
+    if options.flag("--du") {
+        println!("{}", day07::du_report(&fs, &sizes));
     }
 
-    this_dir_size
+    Ok(())
 }
 
-fn parse_dir(dir_name: String, lines: &mut Peekable<Iter<String>>) -> Directory {
-    let mut files = Vec::<File>::new();
-    let mut subdirs = Vec::<Directory>::new();
-
-    _ = lines.next().expect("line containing 'ls' is yielded");
-
-    while let Some(line) = lines.next_if(|l| !l.starts_with("$ ")) {
-        let (p1, p2) = line.split_ascii_whitespace().collect_tuple().expect("Either dir or file");
-        if p1 == "dir" {
-
-        }
-        else {
-            let size = p1.parse::<usize>().expect("Size of file is found in first part");
-            files.push(File {size, name:p2.to_string()})
-        }
-    }
-
-    while let Some(line) = lines.next() {
-        if line == "$ cd .." {
-            break;
-        }
-        else {
-            let subdir_name = parse_dirname_from_cd(line);
-            subdirs.push(parse_dir(subdir_name, lines));
-        }
+fn main() -> ExitCode {
+    match Options::from_env(&["--tree", "--du"], &["--limit"]) {
+        Ok(options) if options.is_empty() => run_stdin::<Day07>(Part::A),
+        Ok(options) => exit_code(run(&options)),
+        Err(e) => exit_code(Err(e.into())),
     }
-
-    Directory { name: dir_name, files, subdirs }
-}
-
-fn main() {
-    let lines =std::io::stdin()
-        .lines()
-        .map(|maybe_line| maybe_line.unwrap())
-        .collect::<Vec<String>>();
-
-    let mut k = lines.iter().peekable();
-    let root_name = parse_dirname_from_cd(k.next().expect("First line is root"));
-    let dir = parse_dir(root_name, &mut k);
-
-    let mut result = 0usize;
-    _ = accumulate_total_size_of_small_directories(&mut result, &dir);
-
-    println!("{}", result);
 }
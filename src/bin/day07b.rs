@@ -1,107 +1,50 @@
-use core::slice::Iter;
-use std::iter::Peekable;
-use itertools::Itertools;
-
-#[derive(Debug)]
-struct File {
-    size: usize,
-    #[allow(dead_code)]
-    name: String
-}
-
-#[derive(Debug)]
-struct Directory {
-    #[allow(dead_code)]
-    name: String,
-    files: Vec<File>,
-    subdirs: Vec<Directory>
-}
-
-impl Directory {
-    fn files_size(&self) -> usize {
-        self.files.iter().map(|f| f.size).sum::<usize>()
+use std::error::Error;
+use std::process::ExitCode;
+
+use aoc22::day07::{self, Day07};
+use aoc22::shared::cli::Options;
+use aoc22::shared::runner::{exit_code, parse_stdin, run_stdin, Part};
+
+/// `--disk <bytes>` and `--needed <bytes>` change the disk size and the space to free, the
+/// directory to delete is then named as well. `--tree` and `--du` print the directory tree below
+/// the answer.
+fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    let fs = parse_stdin::<Day07>()?;
+    let sizes = fs.sizes();
+
+    let disk = options
+        .parsed::<usize>("--disk")?
+        .unwrap_or(day07::DISK_SIZE);
+    let needed = options
+        .parsed::<usize>("--needed")?
+        .unwrap_or(day07::NEEDED_SPACE);
+    let dir = day07::directory_to_delete(&fs, &sizes, disk, needed)
+        .ok_or("No directory frees enough space")?;
+    println!("{}", sizes[dir]);
+
+    if options.value("--disk").is_some() || options.value("--needed").is_some() {
+        println!(
+            "Deleting {} frees {}",
+            fs.path(dir),
+            day07::human_size(sizes[dir])
+        );
     }
-}
 
-fn parse_dirname_from_cd(s: &str) -> String {
-    let (_, cmd, dir_name) = s
-        .split_ascii_whitespace()
-        .collect_tuple()
-        .expect("Line can be collected to 3-element tuple");
-
-    if cmd != "cd" {
-        panic!("Expected cd command");
+    if options.flag("--tree") {
+        println!("{}", day07::tree(&fs, &sizes));
     }
 
-    dir_name.to_string()
-}
-
-fn get_sizes_of_dirs(result: &mut Vec<usize>,  dir: &Directory) -> usize {
-    let mut this_dir_size = 0usize;
-    if !dir.subdirs.is_empty() {
-        dir.subdirs.iter().for_each(
-            |sd| { this_dir_size += get_sizes_of_dirs(result, sd); }
-        )
+    if options.flag("--du") {
+        println!("{}", day07::du_report(&fs, &sizes));
     }
 
-    this_dir_size += dir.files_size();
-
-    (*result).push(this_dir_size);
-
-    this_dir_size
+    Ok(())
 }
 
-fn parse_dir(dir_name: String, lines: &mut Peekable<Iter<String>>) -> Directory {
-    let mut files = Vec::<File>::new();
-    let mut subdirs = Vec::<Directory>::new();
-
-    _ = lines.next().expect("line containing 'ls' is yielded");
-
-    while let Some(line) = lines.next_if(|l| !l.starts_with("$ ")) {
-        let (p1, p2) = line.split_ascii_whitespace().collect_tuple().expect("Either dir or file");
-        if p1 == "dir" {
-
-        }
-        else {
-            let size = p1.parse::<usize>().expect("Size of file is found in first part");
-            files.push(File {size, name:p2.to_string()})
-        }
-    }
-
-    while let Some(line) = lines.next() {
-        if line == "$ cd .." {
-            break;
-        }
-        else {
-            let subdir_name = parse_dirname_from_cd(line);
-            subdirs.push(parse_dir(subdir_name, lines));
-        }
+fn main() -> ExitCode {
+    match Options::from_env(&["--tree", "--du"], &["--disk", "--needed"]) {
+        Ok(options) if options.is_empty() => run_stdin::<Day07>(Part::B),
+        Ok(options) => exit_code(run(&options)),
+        Err(e) => exit_code(Err(e.into())),
     }
-
-    Directory { name: dir_name, files, subdirs }
-}
-
-fn main() {
-    let lines =std::io::stdin()
-        .lines()
-        .map(|maybe_line| maybe_line.unwrap())
-        .collect::<Vec<String>>();
-
-    let mut k = lines.iter().peekable();
-    let root_name = parse_dirname_from_cd(k.next().expect("First line is root"));
-    let dir = parse_dir(root_name, &mut k);
-
-    let mut sizes = Vec::<usize>::new();
-    let total_size = get_sizes_of_dirs(&mut sizes, &dir);
-
-    let limit = 70_000_000usize;
-    let unused = limit - total_size;
-
-    let smallest_to_delete_size = sizes
-        .iter()
-        .sorted()
-        .find(|&size| unused + size >= 30_000_000)
-        .expect("There is such a size");
-
-    println!("{smallest_to_delete_size}");
 }
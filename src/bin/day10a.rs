@@ -1,33 +1,26 @@
-use itertools::Itertools;
+use std::error::Error;
+use std::io;
+use std::process::ExitCode;
 
-fn main() {
-    let mut x = 1i32;
-    let mut cycle_values = Vec::<i32>::new();
+use aoc22::day10::{Day10, Debugger};
+use aoc22::shared::cli::Options;
+use aoc22::shared::runner::{exit_code, run_stdin, Part};
+use aoc22::shared::Solver;
 
-    for maybe_line in std::io::stdin().lines() {
-        let line = maybe_line.expect("Successful read");
-        if line.starts_with("noop") {
-            cycle_values.push(x);
-        }
-        else {
-            let (_, value_str) = line
-                .split_ascii_whitespace()
-                .collect_tuple()
-                .expect("Pair");
+/// `--debug <program>` loads the program from a file and reads debugger commands from stdin.
+fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    let file = options.value("--debug").ok_or("Missing --debug")?;
+    let source = std::fs::read_to_string(file).map_err(|e| format!("Cannot read {file}: {e}"))?;
+    let program = Day10::parse(&source).map_err(|e| e.in_day(Day10::DAY))?;
+    Debugger::new(&program).repl(io::stdin().lock(), io::stdout())?;
 
-            let value = value_str.parse::<i32>().expect("Successful parse");
-            cycle_values.push(x);
-            cycle_values.push(x);
-            x += value;
-        }
-    }
-
-    let result = vec![20, 60, 100, 140, 180, 220]
-        .iter()
-        .map(|&i| {
-            i * cycle_values[i - 1] as usize
-        })
-        .sum::<usize>();
+    Ok(())
+}
 
-    println!("{result}");
+fn main() -> ExitCode {
+    match Options::from_env(&[], &["--debug"]) {
+        Ok(options) if options.is_empty() => run_stdin::<Day10>(Part::A),
+        Ok(options) => exit_code(run(&options)),
+        Err(e) => exit_code(Err(e.into())),
+    }
 }
@@ -1,47 +1,45 @@
-use itertools::Itertools;
+use std::error::Error;
+use std::io;
+use std::process::ExitCode;
 
-fn main() {
-    let mut x = 1i32;
-    let mut cycle_values = Vec::<i32>::new();
+use aoc22::day10::{self, Day10, Debugger};
+use aoc22::shared::cli::Options;
+use aoc22::shared::runner::{exit_code, parse_stdin, run_stdin, Part};
+use aoc22::shared::Solver;
 
-    for maybe_line in std::io::stdin().lines() {
-        let line = maybe_line.expect("Successful read");
-        if line.starts_with("noop") {
-            cycle_values.push(x);
-        }
-        else {
-            let (_, value_str) = line
-                .split_ascii_whitespace()
-                .collect_tuple()
-                .expect("Pair");
+/// `--crt` draws the picture below the letters read from it. When some glyphs are not
+/// recognised the picture is drawn before the error is reported.
+fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    if let Some(file) = options.value("--debug") {
+        return debug(file);
+    }
 
-            let value = value_str.parse::<i32>().expect("Successful parse");
-            cycle_values.push(x);
-            cycle_values.push(x);
-            x += value;
-        }
+    let program = parse_stdin::<Day10>()?;
+    let crt = day10::render_crt(&program).ok_or("The input has no answer")?;
+    let recognised = day10::recognise(&crt);
+    if let Ok(letters) = &recognised {
+        println!("{letters}");
+    }
+    if options.flag("--crt") {
+        println!("{crt}");
     }
 
-    let width = 40;
-    let height = 6;
+    Ok(recognised.map(|_| ())?)
+}
+
+/// Loads the program from a file and reads debugger commands from stdin.
+fn debug(file: &str) -> Result<(), Box<dyn Error>> {
+    let source = std::fs::read_to_string(file).map_err(|e| format!("Cannot read {file}: {e}"))?;
+    let program = Day10::parse(&source).map_err(|e| e.in_day(Day10::DAY))?;
+    Debugger::new(&program).repl(io::stdin().lock(), io::stdout())?;
 
-    let mut pixels = cycle_values
-        .iter()
-        .enumerate()
-        .map(|(i, &x)| {
-            if (i % width).abs_diff(x as usize) <= 1 {
-                '#'
-            }
-            else {
-                '.'
-            }
-        });
+    Ok(())
+}
 
-    for _ in 0..height {
-        for _ in 0..width {
-            print!("{}", pixels.next().expect("Value is yielded"));
-        }
-        println!();
+fn main() -> ExitCode {
+    match Options::from_env(&["--crt"], &["--debug"]) {
+        Ok(options) if options.is_empty() => run_stdin::<Day10>(Part::B),
+        Ok(options) => exit_code(run(&options)),
+        Err(e) => exit_code(Err(e.into())),
     }
 }
-
@@ -1,5 +1 @@
-use cgmath::Quaternion;
-
-fn main() {
-    
-}
+fn main() {}
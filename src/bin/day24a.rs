@@ -1,5 +1,8 @@
-fn main() {
-    for line in std::io::stdin().lines() {
-        println!("{}", line.unwrap());
-    }
+use std::process::ExitCode;
+
+use aoc22::day24::Day24;
+use aoc22::shared::runner::{run_stdin, Part};
+
+fn main() -> ExitCode {
+    run_stdin::<Day24>(Part::A)
 }
@@ -0,0 +1,44 @@
+use itertools::Itertools;
+
+use crate::shared::parse::{number, parse_lines};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day01;
+
+/// Total calories carried by every elf, in the order of the input.
+pub fn calories_per_elf(input: &str) -> Result<Vec<i32>, ParseError> {
+    let items = parse_lines(input, |line| {
+        let item = line.trim();
+        if item.is_empty() {
+            Ok(None)
+        } else {
+            number::<i32>(line, item).map(Some)
+        }
+    })?;
+
+    Ok(items
+        .split(|item| item.is_none())
+        .filter(|elf| !elf.is_empty())
+        .map(|elf| elf.iter().flatten().sum::<i32>())
+        .collect())
+}
+
+impl Solver for Day01 {
+    const DAY: u32 = 1;
+
+    type Input = Vec<i32>;
+    type AnswerA = i32;
+    type AnswerB = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        calories_per_elf(input)
+    }
+
+    fn part_a(input: &Self::Input) -> Option<i32> {
+        input.iter().copied().max()
+    }
+
+    fn part_b(input: &Self::Input) -> Option<i32> {
+        Some(input.iter().sorted().rev().take(3).sum::<i32>())
+    }
+}
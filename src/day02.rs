@@ -0,0 +1,97 @@
+use crate::shared::parse::{parse_lines, split_once, LineError};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day02;
+
+// Scores are written as outcome + shape to mirror the puzzle statement
+#[allow(clippy::identity_op)]
+pub fn get_score_a(op_choice: char, my_choice: char) -> i32 {
+    match op_choice {
+        'A' => match my_choice {
+            'X' => 3 + 1,
+            'Y' => 6 + 2,
+            'Z' => 0 + 3,
+            _ => panic!("My choice is invalid"),
+        },
+        'B' => match my_choice {
+            'X' => 0 + 1,
+            'Y' => 3 + 2,
+            'Z' => 6 + 3,
+            _ => panic!("My choice is invalid"),
+        },
+        'C' => match my_choice {
+            'X' => 6 + 1,
+            'Y' => 0 + 2,
+            'Z' => 3 + 3,
+            _ => panic!("My choice is invalid"),
+        },
+        _ => panic!("Invalid opponent choice"),
+    }
+}
+
+#[allow(clippy::identity_op)]
+pub fn get_score_b(op_choice: char, my_choice: char) -> i32 {
+    match op_choice {
+        'A' => match my_choice {
+            'X' => 0 + 3,
+            'Y' => 3 + 1,
+            'Z' => 6 + 2,
+            _ => panic!("My choice is invalid"),
+        },
+        'B' => match my_choice {
+            'X' => 0 + 1,
+            'Y' => 3 + 2,
+            'Z' => 6 + 3,
+            _ => panic!("My choice is invalid"),
+        },
+        'C' => match my_choice {
+            'X' => 0 + 2,
+            'Y' => 3 + 3,
+            'Z' => 6 + 1,
+            _ => panic!("My choice is invalid"),
+        },
+        _ => panic!("Invalid opponent choice"),
+    }
+}
+
+fn parse_round(line: &str) -> Result<(char, char), LineError> {
+    let (op_choice, my_choice) = split_once(line, line.trim(), " ")?;
+    let choice = |part: &str, valid: &str| match part.chars().collect::<Vec<char>>()[..] {
+        [c] if valid.contains(c) => Ok(c),
+        _ => Err(LineError::at(
+            line,
+            part,
+            format!("expected one of {valid}, found '{part}'"),
+        )),
+    };
+
+    Ok((choice(op_choice, "ABC")?, choice(my_choice, "XYZ")?))
+}
+
+fn total_score(rounds: &[(char, char)], get_score: fn(char, char) -> i32) -> i32 {
+    rounds
+        .iter()
+        .map(|&(op_choice, my_choice)| get_score(op_choice, my_choice))
+        .sum::<i32>()
+}
+
+impl Solver for Day02 {
+    const DAY: u32 = 2;
+
+    /// Pairs of the opponent's choice and the second column of the strategy guide
+    type Input = Vec<(char, char)>;
+    type AnswerA = i32;
+    type AnswerB = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, parse_round)
+    }
+
+    fn part_a(input: &Self::Input) -> Option<i32> {
+        Some(total_score(input, get_score_a))
+    }
+
+    fn part_b(input: &Self::Input) -> Option<i32> {
+        Some(total_score(input, get_score_b))
+    }
+}
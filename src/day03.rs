@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+
+use crate::shared::parse::{parse_lines, LineError};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day03;
+
+pub fn get_priority(c: char) -> i32 {
+    if c.is_ascii_uppercase() {
+        (c as i32) - (64 - 26)
+    } else if c.is_ascii_lowercase() {
+        (c as i32) - 96
+    } else {
+        panic!("Unexpected character");
+    }
+}
+
+impl Solver for Day03 {
+    const DAY: u32 = 3;
+
+    type Input = Vec<String>;
+    type AnswerA = i32;
+    type AnswerB = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |line| {
+            match line.find(|c: char| !c.is_ascii_alphabetic()) {
+                Some(i) => Err(LineError::new(i + 1, "expected only item letters")),
+                None => Ok(line.to_string()),
+            }
+        })
+    }
+
+    fn part_a(input: &Self::Input) -> Option<i32> {
+        let result = input
+            .iter()
+            .map(|line| {
+                let left_compartment: HashSet<char> = line.chars().take(line.len() / 2).collect();
+                let right_compartment: HashSet<char> = line.chars().skip(line.len() / 2).collect();
+                let i: Vec<&char> = left_compartment.intersection(&right_compartment).collect();
+                // Only one type should be found in both compartments
+                match i[..] {
+                    [&c] => Some(get_priority(c)),
+                    _ => None,
+                }
+            })
+            .sum::<Option<i32>>();
+
+        result
+    }
+
+    fn part_b(input: &Self::Input) -> Option<i32> {
+        let result = input
+            .chunks(3)
+            .map(|chunk| {
+                let bags = chunk
+                    .iter()
+                    .map(|line| line.chars().collect::<HashSet<char>>())
+                    .collect::<Vec<HashSet<char>>>();
+
+                if bags.len() != 3 {
+                    return None;
+                }
+
+                let i1: HashSet<char> = bags[0].intersection(&bags[1]).copied().collect();
+                let i2: Vec<&char> = i1.intersection(&bags[2]).collect();
+                // Only one type should be found in all three bags
+                match i2[..] {
+                    [&c] => Some(get_priority(c)),
+                    _ => None,
+                }
+            })
+            .sum::<Option<i32>>();
+
+        result
+    }
+}
@@ -0,0 +1,54 @@
+use crate::shared::parse::{number, parse_lines, split_once, LineError};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day04;
+
+pub type Assignment = (i32, i32);
+
+fn parse_assignment(line: &str, s: &str) -> Result<Assignment, LineError> {
+    let (sp1, sp2) = split_once(line, s, "-")?;
+
+    Ok((number(line, sp1)?, number(line, sp2)?))
+}
+
+pub fn is_inside(some: &Assignment, other: &Assignment) -> bool {
+    let (l1, u1) = some;
+    let (l2, u2) = other;
+
+    l1 >= l2 && u1 <= u2
+}
+
+pub fn do_overlap(some: &Assignment, other: &Assignment) -> bool {
+    let (l1, u1) = some;
+    let (l2, u2) = other;
+
+    u1 >= l2 && l1 <= u2
+}
+
+impl Solver for Day04 {
+    const DAY: u32 = 4;
+
+    type Input = Vec<(Assignment, Assignment)>;
+    type AnswerA = usize;
+    type AnswerB = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |line| {
+            let (p1, p2) = split_once(line, line, ",")?;
+            Ok((parse_assignment(line, p1)?, parse_assignment(line, p2)?))
+        })
+    }
+
+    fn part_a(input: &Self::Input) -> Option<usize> {
+        Some(
+            input
+                .iter()
+                .filter(|(a1, a2)| is_inside(a1, a2) || is_inside(a2, a1))
+                .count(),
+        )
+    }
+
+    fn part_b(input: &Self::Input) -> Option<usize> {
+        Some(input.iter().filter(|(a1, a2)| do_overlap(a1, a2)).count())
+    }
+}
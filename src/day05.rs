@@ -0,0 +1,461 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::ops::Range;
+
+use crate::shared::cli::{Cli, Options};
+use crate::shared::parse::{column_of, number, split_once, strip_prefix, LineError};
+use crate::shared::runner::{parse_input, Part};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day05;
+
+/// Stacks from left to right, the top crate is the last element.
+pub type Stacks = Vec<Vec<char>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub count: usize,
+    /// Stacks are numbered from 1 like in the drawing
+    pub from: usize,
+    pub to: usize,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.count, self.from, self.to)
+    }
+}
+
+pub struct Input {
+    pub stacks: Stacks,
+    pub instructions: Vec<Instruction>,
+}
+
+fn parse_instruction(line: &str, stack_count: usize) -> Result<Instruction, LineError> {
+    let rest = strip_prefix(line, line, "move ")?;
+    let (count, rest) = split_once(line, rest, " from ")?;
+    let (from, to) = split_once(line, rest, " to ")?;
+
+    let stack = |part: &str| {
+        let n = number::<usize>(line, part)?;
+        if (1..=stack_count).contains(&n) {
+            Ok(n)
+        } else {
+            Err(LineError::at(
+                line,
+                part,
+                format!("expected a stack between 1 and {stack_count}"),
+            ))
+        }
+    };
+
+    Ok(Instruction {
+        count: number(line, count)?,
+        from: stack(from)?,
+        to: stack(to)?,
+    })
+}
+
+/// The order in which the crates lifted by one instruction land on the target stack.
+pub trait MoveStrategy {
+    /// `lifted` comes bottom crate first, as the crates were stacked.
+    fn arrange(&self, lifted: &mut [char]);
+}
+
+/// Moves crates one at a time, so they land in reverse order.
+pub struct CrateMover9000;
+
+impl MoveStrategy for CrateMover9000 {
+    fn arrange(&self, lifted: &mut [char]) {
+        lifted.reverse();
+    }
+}
+
+/// Moves all the crates at once, so they keep their order.
+pub struct CrateMover9001;
+
+impl MoveStrategy for CrateMover9001 {
+    fn arrange(&self, _lifted: &mut [char]) {}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotEnoughCrates {
+    pub instruction: Instruction,
+    pub available: usize,
+}
+
+impl fmt::Display for NotEnoughCrates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot {}, stack {} has only {} crates",
+            self.instruction, self.instruction.from, self.available
+        )
+    }
+}
+
+impl std::error::Error for NotEnoughCrates {}
+
+/// Stacks of crates rearranged by a crane that moves them according to `S`.
+pub struct Crane<S: MoveStrategy> {
+    pub stacks: Stacks,
+    strategy: S,
+}
+
+impl<S: MoveStrategy> Crane<S> {
+    pub fn new(stacks: Stacks, strategy: S) -> Crane<S> {
+        Crane { stacks, strategy }
+    }
+
+    /// Carries out one instruction, the stacks are left untouched when it cannot be done.
+    pub fn apply(&mut self, instruction: &Instruction) -> Result<(), NotEnoughCrates> {
+        let from = &mut self.stacks[instruction.from - 1];
+        let split = from
+            .len()
+            .checked_sub(instruction.count)
+            .ok_or(NotEnoughCrates {
+                instruction: *instruction,
+                available: from.len(),
+            })?;
+
+        let mut lifted = from.split_off(split);
+        self.strategy.arrange(&mut lifted);
+        self.stacks[instruction.to - 1].extend(lifted);
+
+        Ok(())
+    }
+
+    /// Carries out the instructions in order, `on_step` sees the crane after each of them.
+    pub fn run(
+        &mut self,
+        instructions: &[Instruction],
+        mut on_step: impl FnMut(&Instruction, &Self),
+    ) -> Result<(), NotEnoughCrates> {
+        for instruction in instructions {
+            self.apply(instruction)?;
+            on_step(instruction, self);
+        }
+
+        Ok(())
+    }
+
+    /// Crates on top of the stacks, empty stacks are skipped.
+    pub fn top_crates(&self) -> String {
+        self.stacks.iter().filter_map(|s| s.last()).collect()
+    }
+}
+
+/// Crates are drawn as `[A]` in cells as wide as the widest stack label, at least 3, with one
+/// space between cells. Up to 9 stacks this is exactly the layout of the puzzle input.
+fn cell_width(stack_count: usize) -> usize {
+    stack_count.to_string().len().max(3)
+}
+
+/// Draws the stacks the way the puzzle input does, with trailing spaces trimmed.
+pub fn print_drawing(stacks: &[Vec<char>]) -> String {
+    let width = cell_width(stacks.len());
+    let height = stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+    let line = |cells: Vec<String>| {
+        cells
+            .iter()
+            .map(|cell| format!("{cell:^width$}"))
+            .collect::<Vec<String>>()
+            .join(" ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = (0..height)
+        .rev()
+        .map(|level| {
+            line(
+                stacks
+                    .iter()
+                    .map(|s| s.get(level).map_or(String::new(), |c| format!("[{c}]")))
+                    .collect(),
+            )
+        })
+        .collect::<Vec<String>>();
+    lines.push(line((1..=stacks.len()).map(|i| i.to_string()).collect()));
+
+    lines.join("\n")
+}
+
+/// Reads a drawing made of `lines`, the last of which labels the stacks `1 2 3 ...`. Every
+/// crate belongs to the stack whose label it overlaps, so any cell width and ragged or
+/// trimmed lines are accepted.
+pub fn parse_drawing(lines: &[&str]) -> Result<Stacks, ParseError> {
+    let Some((label_line, crate_lines)) = lines.split_last() else {
+        return Err(ParseError::end_of_input(
+            "",
+            "expected a drawing of the stacks",
+        ));
+    };
+    let label_number = crate_lines.len() + 1;
+
+    // Columns covered by every label, counted from 1 like the columns of errors
+    let labels = label_line
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, label)| {
+            let n = number::<usize>(label_line, label)
+                .map_err(|e| e.on_line(label_number, label_line))?;
+            if n != i + 1 {
+                return Err(LineError::at(
+                    label_line,
+                    label,
+                    format!("expected stack label {}", i + 1),
+                )
+                .on_line(label_number, label_line));
+            }
+            let column = column_of(label_line, label);
+
+            Ok(column..column + label.len())
+        })
+        .collect::<Result<Vec<Range<usize>>, ParseError>>()?;
+
+    if labels.is_empty() {
+        return Err(ParseError::new(
+            label_number,
+            1,
+            label_line,
+            "expected stack labels",
+        ));
+    }
+
+    let mut stacks: Stacks = vec![Vec::new(); labels.len()];
+
+    for (i, line) in crate_lines.iter().enumerate().rev() {
+        let mut taken = vec![false; labels.len()];
+        for token in line.split_whitespace() {
+            let error = |message: &str| LineError::at(line, token, message).on_line(i + 1, line);
+            let label = match token.as_bytes() {
+                [b'[', c, b']'] if c.is_ascii_alphanumeric() => *c as char,
+                _ => return Err(error("expected a crate like '[A]'")),
+            };
+
+            let column = column_of(line, token);
+            let span = column..column + token.len();
+            let stack = labels
+                .iter()
+                .position(|l| l.start < span.end && span.start < l.end)
+                .ok_or_else(|| error("expected a crate above a stack label"))?;
+
+            if std::mem::replace(&mut taken[stack], true) {
+                return Err(error("expected one crate per stack in a line"));
+            }
+            if stacks[stack].len() != crate_lines.len() - 1 - i {
+                return Err(error("expected a crate resting on another one"));
+            }
+            stacks[stack].push(label);
+        }
+    }
+
+    Ok(stacks)
+}
+
+/// The top crates after all instructions with crates moved according to `strategy`.
+pub fn rearrange(input: &Input, strategy: impl MoveStrategy) -> Result<String, NotEnoughCrates> {
+    let mut crane = Crane::new(input.stacks.clone(), strategy);
+    crane.run(&input.instructions, |_, _| {})?;
+
+    Ok(crane.top_crates())
+}
+
+/// `--trace` draws the stacks after every instruction before printing the answer.
+pub const CLI: Cli = Cli {
+    flags: &["--trace"],
+    valued: &[],
+    run,
+};
+
+fn run(
+    part: Part,
+    _options: &Options,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let puzzle = parse_input::<Day05>(input)?;
+    match part {
+        Part::A => trace(puzzle, CrateMover9000, output),
+        Part::B => trace(puzzle, CrateMover9001, output),
+    }
+}
+
+/// Writes the stacks before and after every instruction, then the top crates.
+fn trace(
+    puzzle: Input,
+    strategy: impl MoveStrategy,
+    output: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let mut crane = Crane::new(puzzle.stacks, strategy);
+
+    writeln!(output, "{}\n", print_drawing(&crane.stacks))?;
+    for instruction in &puzzle.instructions {
+        crane.apply(instruction)?;
+        writeln!(output, "{instruction}\n{}\n", print_drawing(&crane.stacks))?;
+    }
+    writeln!(output, "{}", crane.top_crates())?;
+
+    Ok(())
+}
+
+impl Solver for Day05 {
+    const DAY: u32 = 5;
+
+    type Input = Input;
+    type AnswerA = String;
+    type AnswerB = String;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        let all_lines = input.lines().collect::<Vec<&str>>();
+
+        let stack_line_count = all_lines.iter().take_while(|l| !l.is_empty()).count();
+        let stacks = parse_drawing(&all_lines[..stack_line_count])?;
+        let stack_count = stacks.len();
+
+        // Both cranes take the same number of crates off every stack, so the heights tell
+        // whether an instruction runs out of crates whichever crane is used
+        let mut heights = stacks.iter().map(|s| s.len()).collect::<Vec<usize>>();
+        let instructions = all_lines
+            .iter()
+            .enumerate()
+            .skip(stack_line_count + 1)
+            .map(|(i, line)| {
+                let instruction =
+                    parse_instruction(line, stack_count).map_err(|e| e.on_line(i + 1, line))?;
+
+                let available = heights[instruction.from - 1];
+                if available < instruction.count {
+                    let error = NotEnoughCrates {
+                        instruction,
+                        available,
+                    };
+                    return Err(ParseError::new(i + 1, 1, line, error.to_string()));
+                }
+                heights[instruction.from - 1] -= instruction.count;
+                heights[instruction.to - 1] += instruction.count;
+
+                Ok(instruction)
+            })
+            .collect::<Result<Vec<Instruction>, ParseError>>()?;
+
+        Ok(Input {
+            stacks,
+            instructions,
+        })
+    }
+
+    fn part_a(input: &Input) -> Option<String> {
+        rearrange(input, CrateMover9000).ok()
+    }
+
+    fn part_b(input: &Input) -> Option<String> {
+        rearrange(input, CrateMover9001).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::random::Random;
+
+    const CRATES: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+    fn random_stacks(random: &mut Random) -> Stacks {
+        let count = 1 + random.below(120);
+        (0..count)
+            .map(|_| {
+                // Every fourth stack is empty
+                let height = random.below(12).saturating_sub(3);
+                (0..height)
+                    .map(|_| CRATES[random.below(CRATES.len())] as char)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn parse(drawing: &str) -> Result<Stacks, ParseError> {
+        parse_drawing(&drawing.lines().collect::<Vec<&str>>())
+    }
+
+    #[test]
+    fn printed_drawings_parse_back() {
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..500 {
+            let stacks = random_stacks(&mut random);
+            let drawing = print_drawing(&stacks);
+
+            assert_eq!(parse(&drawing).unwrap(), stacks, "{drawing}");
+        }
+    }
+
+    #[test]
+    fn padded_drawings_parse_back() {
+        let mut random = Random(0x2545_f491_4f6c_dd1d);
+        for _ in 0..200 {
+            let stacks = random_stacks(&mut random);
+            let drawing = print_drawing(&stacks);
+            let width = drawing.lines().map(str::len).max().unwrap();
+            let padded = drawing
+                .lines()
+                .map(|line| format!("{line:width$}"))
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            assert_eq!(parse(&padded).unwrap(), stacks, "{padded}");
+        }
+    }
+
+    #[test]
+    fn multi_digit_labels_round_trip() {
+        let mut stacks = vec![Vec::new(); 12];
+        stacks[9] = vec!['A', 'B'];
+        stacks[11] = vec!['C'];
+
+        let drawing = print_drawing(&stacks);
+        assert_eq!(
+            drawing.lines().last(),
+            Some(" 1   2   3   4   5   6   7   8   9  10  11  12")
+        );
+        assert_eq!(parse(&drawing).unwrap(), stacks);
+    }
+
+    const PUZZLE_DRAWING: &str = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3";
+
+    #[test]
+    fn puzzle_drawing_round_trips() {
+        let stacks = parse(PUZZLE_DRAWING).unwrap();
+
+        assert_eq!(stacks, [vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]);
+        assert_eq!(print_drawing(&stacks), PUZZLE_DRAWING);
+    }
+
+    #[test]
+    fn trace_draws_every_step() {
+        let example = format!("{PUZZLE_DRAWING}\n\nmove 1 from 2 to 1\nmove 3 from 1 to 3");
+        let trace = CLI.run_on(Part::B, &["--trace"], &example).unwrap();
+
+        assert_eq!(
+            trace.split("\n\n").collect::<Vec<&str>>(),
+            [
+                PUZZLE_DRAWING,
+                "move 1 from 2 to 1\n[D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3",
+                "move 3 from 1 to 3\n        [D]\n        [N]\n    [C] [Z]\n    [M] [P]\n 1   2   3",
+                "CD\n",
+            ]
+        );
+        assert!(CLI
+            .run_on(Part::A, &["--trace"], &example)
+            .unwrap()
+            .ends_with("\n\nCZ\n"));
+    }
+
+    #[test]
+    fn floating_crates_are_rejected() {
+        let e = parse("[A]\n\n 1").unwrap_err();
+
+        assert_eq!((e.line, e.column), (1, 1));
+    }
+}
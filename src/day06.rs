@@ -0,0 +1,316 @@
+use std::error::Error;
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use crate::shared::cli::{Cli, Options};
+use crate::shared::runner::Part;
+use crate::shared::{ParseError, Solver};
+
+pub struct Day06;
+
+pub const START_OF_PACKET: usize = 4;
+pub const START_OF_MESSAGE: usize = 14;
+
+/// Watches a stream byte by byte for windows of `size` bytes that are all different. Keeps a count
+/// of every byte value in the window, so each byte costs O(1) and nothing is allocated after
+/// construction.
+#[derive(Debug, Clone)]
+pub struct MarkerDetector {
+    size: usize,
+    /// The last `size` bytes as a ring, `slot` is where the next byte goes
+    window: Vec<u8>,
+    slot: usize,
+    counts: [usize; 256],
+    distinct: usize,
+    position: usize,
+}
+
+impl MarkerDetector {
+    pub fn new(size: usize) -> MarkerDetector {
+        assert!(size > 0, "Marker size must be positive");
+        MarkerDetector {
+            size,
+            window: vec![0; size],
+            slot: 0,
+            counts: [0; 256],
+            distinct: 0,
+            position: 0,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of bytes seen so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Feeds the next byte, returns the position right after it when it completes a marker.
+    pub fn push(&mut self, byte: u8) -> Option<usize> {
+        let slot = self.slot;
+        self.slot = if slot + 1 == self.size { 0 } else { slot + 1 };
+        if self.position >= self.size {
+            let old = self.window[slot] as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 0 {
+                self.distinct -= 1;
+            }
+        }
+
+        self.window[slot] = byte;
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 1 {
+            self.distinct += 1;
+        }
+        self.position += 1;
+
+        if self.distinct == self.size {
+            Some(self.position)
+        } else {
+            None
+        }
+    }
+}
+
+/// Positions right after every marker in a stream, in order. Windows may overlap, so a run of
+/// different bytes longer than the marker yields a position for each byte past the first marker.
+///
+/// Like the puzzle input, the signal is one line: the stream ends at the first `\n` or `\r`.
+pub struct Markers<R> {
+    reader: BufReader<R>,
+    detector: MarkerDetector,
+    ended: bool,
+}
+
+pub fn markers<R: Read>(reader: R, size: usize) -> Markers<R> {
+    Markers {
+        reader: BufReader::new(reader),
+        detector: MarkerDetector::new(size),
+        ended: false,
+    }
+}
+
+impl<R: Read> Iterator for Markers<R> {
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<io::Result<usize>> {
+        while !self.ended {
+            let buffer = match self.reader.fill_buf() {
+                Ok([]) => return None,
+                Ok(buffer) => buffer,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Some(Err(e)),
+            };
+
+            let mut used = 0;
+            let mut found = None;
+            for &byte in buffer {
+                used += 1;
+                if matches!(byte, b'\n' | b'\r') {
+                    self.ended = true;
+                    break;
+                }
+                found = self.detector.push(byte);
+                if found.is_some() {
+                    break;
+                }
+            }
+            self.reader.consume(used);
+
+            if let Some(position) = found {
+                return Some(Ok(position));
+            }
+        }
+
+        None
+    }
+}
+
+/// Position right after the first `size` characters that are all different.
+pub fn find_marker(signal: &str, size: usize) -> Option<usize> {
+    let mut detector = MarkerDetector::new(size);
+    signal.bytes().find_map(|byte| detector.push(byte))
+}
+
+/// Reads the first line of the input as a stream of any length. `--size <n>` changes the marker
+/// size and `--all` prints every marker instead of only the first.
+pub const CLI: Cli = Cli {
+    flags: &["--all"],
+    valued: &["--size"],
+    run,
+};
+
+fn run(
+    part: Part,
+    options: &Options,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let size = match part {
+        Part::A => START_OF_PACKET,
+        Part::B => START_OF_MESSAGE,
+    };
+    let size = options.parsed::<usize>("--size")?.unwrap_or(size);
+    if size == 0 {
+        return Err("The marker size must be positive".into());
+    }
+
+    let mut found = false;
+    for position in markers(input, size) {
+        writeln!(output, "{}", position?)?;
+        found = true;
+        if !options.flag("--all") {
+            break;
+        }
+    }
+
+    if found {
+        Ok(())
+    } else {
+        Err("The stream has no marker".into())
+    }
+}
+
+impl Solver for Day06 {
+    const DAY: u32 = 6;
+
+    type Input = String;
+    type AnswerA = usize;
+    type AnswerB = usize;
+
+    fn parse(input: &str) -> Result<String, ParseError> {
+        match input.lines().next() {
+            Some(line) => Ok(line.to_string()),
+            None => Err(ParseError::end_of_input(input, "expected the datastream")),
+        }
+    }
+
+    fn part_a(input: &String) -> Option<usize> {
+        find_marker(input, START_OF_PACKET)
+    }
+
+    fn part_b(input: &String) -> Option<usize> {
+        find_marker(input, START_OF_MESSAGE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::random::Random;
+
+    /// Hands out at most `chunk` bytes per read, so markers straddle the buffer boundaries.
+    struct Chunked<'a> {
+        bytes: &'a [u8],
+        chunk: usize,
+    }
+
+    impl Read for Chunked<'_> {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            let n = self.chunk.min(buffer.len()).min(self.bytes.len());
+            buffer[..n].copy_from_slice(&self.bytes[..n]);
+            self.bytes = &self.bytes[n..];
+            Ok(n)
+        }
+    }
+
+    fn all_markers(signal: &str, size: usize, chunk: usize) -> Vec<usize> {
+        let reader = Chunked {
+            bytes: signal.as_bytes(),
+            chunk,
+        };
+        markers(reader, size).map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn overlapping_markers_are_all_reported() {
+        assert_eq!(all_markers("abcd", 2, 1), vec![2, 3, 4]);
+        assert_eq!(all_markers("abcabc", 3, 1), vec![3, 4, 5, 6]);
+        assert_eq!(all_markers("aabcdd", 3, 1), vec![4, 5]);
+        assert_eq!(all_markers("aaaa", 1, 1), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn window_longer_than_the_signal_has_no_marker() {
+        assert_eq!(all_markers("abc", 5, 1), Vec::<usize>::new());
+        assert_eq!(all_markers("", 1, 1), Vec::<usize>::new());
+        assert_eq!(find_marker("abc", 5), None);
+    }
+
+    #[test]
+    fn stream_ends_at_the_first_line_break() {
+        assert_eq!(all_markers("zzzzzzzzbc\n", 4, 1), Vec::<usize>::new());
+        assert_eq!(all_markers("abcd\nefgh", 4, 1), vec![4]);
+        assert_eq!(all_markers("zzab\r\ncd", 4, 1), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn options_default_to_the_part() {
+        let signal = "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n";
+
+        assert_eq!(
+            CLI.run_on(Part::A, &["--all"], signal)
+                .unwrap()
+                .lines()
+                .count(),
+            24
+        );
+        assert_eq!(
+            CLI.run_on(Part::B, &["--all"], signal).unwrap(),
+            "19\n25\n26\n27\n28\n29\n30\n"
+        );
+        assert_eq!(
+            CLI.run_on(Part::B, &["--size", "4"], signal).unwrap(),
+            "7\n"
+        );
+        assert!(CLI.run_on(Part::A, &["--size", "0"], signal).is_err());
+        assert!(CLI
+            .run_on(Part::A, &["--size", "4"], "zzzzzzzzbc\n")
+            .is_err());
+    }
+
+    #[test]
+    fn detector_counts_the_bytes_seen() {
+        let mut detector = MarkerDetector::new(3);
+        let found = b"abab".map(|byte| detector.push(byte));
+
+        assert_eq!(found, [None, None, None, None]);
+        assert_eq!((detector.size(), detector.position()), (3, 4));
+        assert_eq!(detector.push(b'c'), Some(5));
+    }
+
+    #[test]
+    fn stream_agrees_with_find_marker() {
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..500 {
+            let length = random.below(40);
+            let alphabet = 1 + random.below(8);
+            let signal = (0..length)
+                .map(|_| (b'a' + random.below(alphabet) as u8) as char)
+                .collect::<String>();
+            let size = 1 + random.below(6);
+
+            // Every window, checked directly
+            let expected = (size..=length)
+                .filter(|&end| {
+                    let window = &signal.as_bytes()[end - size..end];
+                    (1..size).all(|i| !window[..i].contains(&window[i]))
+                })
+                .collect::<Vec<usize>>();
+
+            for chunk in [1, 2, 7, 64] {
+                assert_eq!(
+                    all_markers(&signal, size, chunk),
+                    expected,
+                    "{signal} {size}"
+                );
+            }
+            assert_eq!(find_marker(&signal, size), expected.first().copied());
+
+            let line = format!("{signal}\n{signal}");
+            assert_eq!(all_markers(&line, size, 3), expected);
+        }
+    }
+}
@@ -0,0 +1,621 @@
+use itertools::Itertools;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::shared::parse::{number, split_once, LineError};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day07;
+
+/// Index of a node in the arena of a `FileSystem`.
+pub type NodeId = usize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeKind {
+    File {
+        size: usize,
+    },
+    Directory {
+        /// Children by name, so they come out sorted
+        children: BTreeMap<String, NodeId>,
+        /// Whether `ls` was run in the directory, otherwise its contents are unknown
+        listed: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub name: String,
+    pub parent: Option<NodeId>,
+    pub kind: NodeKind,
+    /// Line of the transcript where the node first appears, 0 for the root
+    pub line: usize,
+}
+
+impl Node {
+    pub fn is_directory(&self) -> bool {
+        matches!(self.kind, NodeKind::Directory { .. })
+    }
+}
+
+/// Output of an `ls` being replayed.
+struct Listing<'a> {
+    /// The `$ ls` line and its number
+    line: &'a str,
+    number: usize,
+    names: HashSet<&'a str>,
+}
+
+/// State of a replayed terminal session.
+struct Session<'a> {
+    cwd: NodeId,
+    /// The last `ls`, `None` when the last line was not part of its output
+    listing: Option<Listing<'a>>,
+}
+
+/// Directory tree rebuilt from a terminal transcript. Nodes live in one arena and refer to each
+/// other by index, a parent always comes before its children.
+#[derive(Debug, Clone)]
+pub struct FileSystem {
+    nodes: Vec<Node>,
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        FileSystem::new()
+    }
+}
+
+impl FileSystem {
+    pub const ROOT: NodeId = 0;
+
+    /// A file system with nothing but the root directory.
+    pub fn new() -> FileSystem {
+        FileSystem {
+            nodes: vec![Node {
+                name: String::new(),
+                parent: None,
+                kind: NodeKind::Directory {
+                    children: BTreeMap::new(),
+                    listed: false,
+                },
+                line: 0,
+            }],
+        }
+    }
+
+    /// Replays the `$ cd` and `$ ls` commands of a transcript in any order. Directories may be
+    /// entered and listed any number of times, as long as every listing of a directory names the
+    /// same entries, the subdirectories entered with `cd` included.
+    pub fn replay(transcript: &str) -> Result<FileSystem, ParseError> {
+        let mut fs = FileSystem::new();
+        let mut session = Session {
+            cwd: FileSystem::ROOT,
+            listing: None,
+        };
+
+        for (i, line) in transcript.lines().enumerate() {
+            if line.starts_with("$ ") {
+                fs.end_listing(&mut session)?;
+            }
+            fs.replay_line(&mut session, i + 1, line)
+                .map_err(|e| e.on_line(i + 1, line))?;
+        }
+        fs.end_listing(&mut session)?;
+
+        Ok(fs)
+    }
+
+    /// Checks that the `ls` just replayed named everything already known to be in the
+    /// directory, from an earlier listing or from entering a subdirectory.
+    fn end_listing(&self, session: &mut Session) -> Result<(), ParseError> {
+        let Some(listing) = session.listing.take() else {
+            return Ok(());
+        };
+
+        let missing = self
+            .children(session.cwd)
+            .find(|&child| !listing.names.contains(self.nodes[child].name.as_str()));
+        match missing {
+            None => Ok(()),
+            Some(child) => {
+                let message = format!(
+                    "{} is not listed, it appears on line {}",
+                    self.path(child),
+                    self.nodes[child].line
+                );
+                Err(LineError::at(listing.line, &listing.line[2..], message)
+                    .on_line(listing.number, listing.line))
+            }
+        }
+    }
+
+    fn replay_line<'a>(
+        &mut self,
+        session: &mut Session<'a>,
+        number_of_line: usize,
+        line: &'a str,
+    ) -> Result<(), LineError> {
+        let at = |part: &str, message: String| LineError::at(line, part, message);
+
+        if let Some(command) = line.strip_prefix("$ ") {
+            if command == "ls" {
+                if let NodeKind::Directory { listed, .. } = &mut self.nodes[session.cwd].kind {
+                    *listed = true;
+                }
+                session.listing = Some(Listing {
+                    line,
+                    number: number_of_line,
+                    names: HashSet::new(),
+                });
+            } else if let Some(target) = command.strip_prefix("cd ") {
+                session.cwd = self
+                    .change_directory(session.cwd, target, number_of_line)
+                    .map_err(|m| at(target, m))?;
+            } else {
+                return Err(at(
+                    command,
+                    format!("expected 'cd' or 'ls', found '{command}'"),
+                ));
+            }
+        } else {
+            let names = &mut session
+                .listing
+                .as_mut()
+                .ok_or_else(|| at(line, "expected a command, output follows only 'ls'".into()))?
+                .names;
+
+            let (p1, name) = split_once(line, line, " ")?;
+            if !names.insert(name) {
+                return Err(at(name, format!("{name} is listed twice")));
+            }
+
+            if p1 == "dir" {
+                self.add_directory(session.cwd, name, number_of_line)
+                    .map_err(|m| at(name, m))?;
+            } else {
+                let size = number::<usize>(line, p1)?;
+                self.add_file(session.cwd, name, size, number_of_line)
+                    .map_err(|m| at(name, m))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn children_mut(&mut self, dir: NodeId) -> &mut BTreeMap<String, NodeId> {
+        match &mut self.nodes[dir].kind {
+            NodeKind::Directory { children, .. } => children,
+            NodeKind::File { .. } => panic!("Node {dir} is not a directory"),
+        }
+    }
+
+    fn add_node(&mut self, dir: NodeId, name: &str, kind: NodeKind, line: usize) -> NodeId {
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            name: name.to_string(),
+            parent: Some(dir),
+            kind,
+            line,
+        });
+        self.children_mut(dir).insert(name.to_string(), id);
+
+        id
+    }
+
+    /// Adds a directory to `dir` unless it already has one of that name.
+    pub fn add_directory(
+        &mut self,
+        dir: NodeId,
+        name: &str,
+        line: usize,
+    ) -> Result<NodeId, String> {
+        match self.child(dir, name) {
+            Some(id) if self.nodes[id].is_directory() => Ok(id),
+            Some(_) => Err(format!("{name} is already listed as a file")),
+            None => {
+                let kind = NodeKind::Directory {
+                    children: BTreeMap::new(),
+                    listed: false,
+                };
+                Ok(self.add_node(dir, name, kind, line))
+            }
+        }
+    }
+
+    /// Adds a file to `dir` unless it already has the same one.
+    pub fn add_file(
+        &mut self,
+        dir: NodeId,
+        name: &str,
+        size: usize,
+        line: usize,
+    ) -> Result<NodeId, String> {
+        match self.child(dir, name).map(|id| (id, &self.nodes[id].kind)) {
+            Some((id, NodeKind::File { size: known })) if *known == size => Ok(id),
+            Some((_, NodeKind::File { size: known })) => {
+                Err(format!("{name} is already listed with size {known}"))
+            }
+            Some((_, NodeKind::Directory { .. })) => {
+                Err(format!("{name} is already listed as a directory"))
+            }
+            None => Ok(self.add_node(dir, name, NodeKind::File { size }, line)),
+        }
+    }
+
+    /// The directory `target` leads to from `cwd`, either an absolute path or one relative to
+    /// `cwd` that can go up with `..`.
+    fn change_directory(
+        &mut self,
+        cwd: NodeId,
+        target: &str,
+        line: usize,
+    ) -> Result<NodeId, String> {
+        let start = if target.starts_with('/') {
+            FileSystem::ROOT
+        } else {
+            cwd
+        };
+
+        target
+            .split('/')
+            .filter(|name| !name.is_empty() && *name != ".")
+            .try_fold(start, |dir, name| match name {
+                ".." => self.nodes[dir]
+                    .parent
+                    .ok_or_else(|| "cannot leave the root directory".to_string()),
+                name => self.enter(dir, name, line),
+            })
+    }
+
+    /// The subdirectory `name` of `dir`. It is created when `dir` was not listed yet, otherwise
+    /// it has to be among the listed entries.
+    fn enter(&mut self, dir: NodeId, name: &str, line: usize) -> Result<NodeId, String> {
+        match self.child(dir, name) {
+            Some(id) if self.nodes[id].is_directory() => Ok(id),
+            Some(_) => Err(format!("{name} is a file")),
+            None if self.is_listed(dir) => {
+                Err(format!("{} has no directory {name}", self.path(dir)))
+            }
+            None => self.add_directory(dir, name, line),
+        }
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id]
+    }
+
+    /// Every node, the root first and every parent before its children.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &Node)> {
+        self.nodes.iter().enumerate()
+    }
+
+    pub fn directories(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes()
+            .filter(|(_, node)| node.is_directory())
+            .map(|(id, _)| id)
+    }
+
+    pub fn is_listed(&self, dir: NodeId) -> bool {
+        matches!(
+            self.nodes[dir].kind,
+            NodeKind::Directory { listed: true, .. }
+        )
+    }
+
+    pub fn child(&self, dir: NodeId, name: &str) -> Option<NodeId> {
+        match &self.nodes[dir].kind {
+            NodeKind::Directory { children, .. } => children.get(name).copied(),
+            NodeKind::File { .. } => None,
+        }
+    }
+
+    /// Children of `dir` sorted by name, nothing for a file.
+    pub fn children(&self, dir: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let children = match &self.nodes[dir].kind {
+            NodeKind::Directory { children, .. } => Some(children.values().copied()),
+            NodeKind::File { .. } => None,
+        };
+
+        children.into_iter().flatten()
+    }
+
+    /// The node at an absolute path such as `/a/e`, `/` being the root.
+    pub fn lookup(&self, path: &str) -> Option<NodeId> {
+        path.strip_prefix('/')?
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(FileSystem::ROOT, |dir, name| self.child(dir, name))
+    }
+
+    /// The absolute path of a node.
+    pub fn path(&self, id: NodeId) -> String {
+        let mut names = Vec::<&str>::new();
+        let mut current = id;
+        while let Some(parent) = self.nodes[current].parent {
+            names.push(&self.nodes[current].name);
+            current = parent;
+        }
+
+        format!("/{}", names.iter().rev().join("/"))
+    }
+
+    /// Total size of every node indexed by its id, directories include everything below them.
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes = self
+            .nodes
+            .iter()
+            .map(|node| match node.kind {
+                NodeKind::File { size } => size,
+                NodeKind::Directory { .. } => 0,
+            })
+            .collect::<Vec<usize>>();
+
+        // Children come after their parents, so going backwards sums up the deepest nodes first
+        for (id, node) in self.nodes.iter().enumerate().skip(1).rev() {
+            let parent = node.parent.expect("Only the root has no parent");
+            sizes[parent] += sizes[id];
+        }
+
+        sizes
+    }
+
+    /// Directories whose contents are unknown because `ls` was never run in them.
+    pub fn unlisted(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.directories().filter(|&dir| !self.is_listed(dir))
+    }
+}
+
+/// Directories up to this size count towards the answer of part a.
+pub const SMALL_DIRECTORY: usize = 100_000;
+pub const DISK_SIZE: usize = 70_000_000;
+/// Free space the update needs.
+pub const NEEDED_SPACE: usize = 30_000_000;
+
+/// Total size of the directories of at most `limit` bytes.
+pub fn sum_of_small_directories(fs: &FileSystem, sizes: &[usize], limit: usize) -> usize {
+    fs.directories()
+        .map(|dir| sizes[dir])
+        .filter(|&size| size <= limit)
+        .sum()
+}
+
+/// The smallest directory whose deletion leaves `needed` bytes free on a disk of `disk` bytes.
+pub fn directory_to_delete(
+    fs: &FileSystem,
+    sizes: &[usize],
+    disk: usize,
+    needed: usize,
+) -> Option<NodeId> {
+    let unused = disk.checked_sub(sizes[FileSystem::ROOT])?;
+
+    fs.directories()
+        .filter(|&dir| unused + sizes[dir] >= needed)
+        .min_by_key(|&dir| sizes[dir])
+}
+
+/// A listing in the style of `tree`, with the total size of every node.
+pub fn tree(fs: &FileSystem, sizes: &[usize]) -> String {
+    fn draw(fs: &FileSystem, sizes: &[usize], dir: NodeId, indent: &str, lines: &mut Vec<String>) {
+        let children = fs.children(dir).collect::<Vec<NodeId>>();
+        for (k, &child) in children.iter().enumerate() {
+            let last = k + 1 == children.len();
+            let node = fs.node(child);
+            let branch = if last { "└── " } else { "├── " };
+            let suffix = if node.is_directory() { "/" } else { "" };
+            lines.push(format!(
+                "{indent}{branch}{}{suffix} ({})",
+                node.name, sizes[child]
+            ));
+
+            let deeper = if last { "    " } else { "│   " };
+            draw(fs, sizes, child, &format!("{indent}{deeper}"), lines);
+        }
+    }
+
+    let mut lines = vec![format!("/ ({})", sizes[FileSystem::ROOT])];
+    draw(fs, sizes, FileSystem::ROOT, "", &mut lines);
+
+    lines.join("\n")
+}
+
+/// Size in the style of `du -h`, rounded up to one decimal below 10 units and to whole units
+/// above.
+pub fn human_size(size: usize) -> String {
+    let units = ["K", "M", "G", "T"];
+    if size < 1024 {
+        return size.to_string();
+    }
+
+    let mut value = size as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < units.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if value < 10.0 {
+        format!("{:.1}{}", (value * 10.0).ceil() / 10.0, units[unit])
+    } else {
+        format!("{}{}", value.ceil(), units[unit])
+    }
+}
+
+/// Every directory with its total size, largest first, in the style of `du -h | sort -hr`.
+pub fn du_report(fs: &FileSystem, sizes: &[usize]) -> String {
+    fs.directories()
+        .sorted_by_key(|&dir| (std::cmp::Reverse(sizes[dir]), fs.path(dir)))
+        .map(|dir| format!("{}\t{}", human_size(sizes[dir]), fs.path(dir)))
+        .join("\n")
+}
+
+impl Solver for Day07 {
+    const DAY: u32 = 7;
+
+    type Input = FileSystem;
+    type AnswerA = usize;
+    type AnswerB = usize;
+
+    fn parse(input: &str) -> Result<FileSystem, ParseError> {
+        let fs = FileSystem::replay(input)?;
+
+        // Sizes of directories that were never listed would be too small
+        if let Some(dir) = fs.unlisted().next() {
+            let node = fs.node(dir);
+            let message = format!("directory {} is never listed", fs.path(dir));
+            return Err(match input.lines().nth(node.line.wrapping_sub(1)) {
+                Some(line) => {
+                    let column = line.rfind(node.name.as_str()).map_or(1, |i| i + 1);
+                    ParseError::new(node.line, column, line, message)
+                }
+                None => ParseError::end_of_input(input, message),
+            });
+        }
+
+        Ok(fs)
+    }
+
+    fn part_a(fs: &FileSystem) -> Option<usize> {
+        Some(sum_of_small_directories(fs, &fs.sizes(), SMALL_DIRECTORY))
+    }
+
+    fn part_b(fs: &FileSystem) -> Option<usize> {
+        let sizes = fs.sizes();
+        let dir = directory_to_delete(fs, &sizes, DISK_SIZE, NEEDED_SPACE)?;
+
+        Some(sizes[dir])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    fn error(transcript: &str) -> (usize, usize, String) {
+        let e = FileSystem::replay(transcript).unwrap_err();
+        (e.line, e.column, e.message)
+    }
+
+    #[test]
+    fn example() {
+        let fs = Day07::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day07::part_a(&fs), Some(95437));
+        assert_eq!(Day07::part_b(&fs), Some(24933642));
+    }
+
+    #[test]
+    fn directories_can_be_entered_before_they_are_listed() {
+        let transcript = "\
+$ cd /a/e
+$ ls
+584 i
+$ cd /
+$ ls
+dir a
+10 b
+$ cd a
+$ ls
+dir e
+20 f";
+        let fs = Day07::parse(transcript).unwrap();
+        let sizes = fs.sizes();
+
+        assert_eq!(sizes[FileSystem::ROOT], 614);
+        assert_eq!(sizes[fs.lookup("/a").unwrap()], 604);
+        assert_eq!(fs.node(fs.lookup("/a/e").unwrap()).line, 1);
+    }
+
+    #[test]
+    fn listings_can_repeat() {
+        let transcript = "$ ls\ndir a\n1 b\n$ cd a\n$ cd /\n$ ls\n1 b\ndir a\n$ cd a\n$ ls";
+        let fs = FileSystem::replay(transcript).unwrap();
+
+        assert_eq!(fs.sizes()[FileSystem::ROOT], 1);
+        assert_eq!(fs.unlisted().count(), 0);
+    }
+
+    #[test]
+    fn listings_have_to_agree() {
+        assert_eq!(
+            error("$ ls\n14848514 b.txt\n$ ls\n100 b.txt"),
+            (
+                4,
+                5,
+                "b.txt is already listed with size 14848514".to_string()
+            )
+        );
+        assert_eq!(
+            error("$ ls\ndir a\n1 b\n$ ls\ndir a\n$ cd a"),
+            (4, 3, "/b is not listed, it appears on line 3".to_string())
+        );
+        assert_eq!(
+            error("$ cd a\n$ cd ..\n$ ls\n1 b"),
+            (3, 3, "/a is not listed, it appears on line 1".to_string())
+        );
+        assert_eq!(
+            error("$ ls\n1 b\n$ cd a"),
+            (3, 6, "/ has no directory a".to_string())
+        );
+        assert_eq!(
+            error("$ ls\ndir a\n1 a"),
+            (3, 3, "a is listed twice".to_string())
+        );
+    }
+
+    #[test]
+    fn directories_have_to_be_listed() {
+        let transcript = "$ cd /\n$ ls\ndir a\ndir b\n$ cd a\n$ ls\n5 c";
+        let fs = FileSystem::replay(transcript).unwrap();
+
+        assert_eq!(
+            fs.unlisted()
+                .map(|dir| fs.path(dir))
+                .collect::<Vec<String>>(),
+            vec!["/b"]
+        );
+
+        let e = Day07::parse(transcript).unwrap_err();
+        assert_eq!((e.line, e.column), (4, 5));
+        assert_eq!(e.message, "directory /b is never listed");
+    }
+
+    #[test]
+    fn lookup_and_path_agree() {
+        let fs = FileSystem::replay(EXAMPLE).unwrap();
+
+        for (id, _) in fs.nodes() {
+            assert_eq!(fs.lookup(&fs.path(id)), Some(id));
+        }
+        assert_eq!(fs.lookup("/"), Some(FileSystem::ROOT));
+        assert_eq!(fs.path(FileSystem::ROOT), "/");
+        assert_eq!(fs.path(fs.lookup("/a/e/i").unwrap()), "/a/e/i");
+        assert_eq!(fs.lookup("a/e"), None);
+        assert_eq!(fs.lookup("/a/x"), None);
+        assert_eq!(fs.lookup("/b.txt/x"), None);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::shared::grid::Position;
+use crate::shared::{Grid, ParseError, Solver};
+
+pub struct Day08;
+
+#[derive(Debug, Clone)]
+pub struct Tree {
+    pub height: i32,
+    pub is_visible: bool,
+}
+
+pub type Map = Grid<Tree>;
+
+fn parse_map(input: &str) -> Result<Map, ParseError> {
+    Grid::parse(input, "a tree height", |c| {
+        c.to_digit(10).map(|height| Tree {
+            height: height as i32,
+            is_visible: false,
+        })
+    })
+}
+
+fn mark_visible_if_taller(p: Position, tallest: &mut i32, map: &mut Map) {
+    let this_tree = &mut map[p];
+    if this_tree.height > *tallest {
+        *tallest = this_tree.height;
+        this_tree.is_visible = true;
+    }
+}
+
+pub fn get_scenic_score(p: Position, map: &Map) -> usize {
+    let origin_height = map[p].height;
+
+    let viewing_distance = |direction| {
+        let mut direction_score = 0usize;
+        for q in map.ray(p, direction) {
+            direction_score += 1;
+            if map[q].height >= origin_height {
+                break;
+            }
+        }
+        direction_score
+    };
+
+    [(0, -1), (0, 1), (-1, 0), (1, 0)]
+        .into_iter()
+        .map(viewing_distance)
+        .product()
+}
+
+impl Solver for Day08 {
+    const DAY: u32 = 8;
+
+    type Input = Map;
+    type AnswerA = usize;
+    type AnswerB = usize;
+
+    fn parse(input: &str) -> Result<Map, ParseError> {
+        parse_map(input)
+    }
+
+    fn part_a(input: &Map) -> Option<usize> {
+        let mut map = input.clone();
+
+        let width = map.width();
+        let height = map.height();
+
+        for x in 0..width {
+            let mut tallest = i32::MIN;
+            for y in 0..height {
+                mark_visible_if_taller((x, y), &mut tallest, &mut map);
+            }
+            tallest = i32::MIN;
+            for y in (0..height).rev() {
+                mark_visible_if_taller((x, y), &mut tallest, &mut map);
+            }
+        }
+
+        for y in 0..height {
+            let mut tallest = i32::MIN;
+            for x in 0..width {
+                mark_visible_if_taller((x, y), &mut tallest, &mut map);
+            }
+            tallest = i32::MIN;
+            for x in (0..width).rev() {
+                mark_visible_if_taller((x, y), &mut tallest, &mut map);
+            }
+        }
+
+        Some(map.iter().filter(|(_, t)| t.is_visible).count())
+    }
+
+    fn part_b(map: &Map) -> Option<usize> {
+        map.positions().map(|p| get_scenic_score(p, map)).max()
+    }
+}
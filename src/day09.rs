@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+
+use crate::shared::parse::{number, parse_lines, split_once, LineError};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day09;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Motion {
+    pub direction: Direction,
+    pub distance: i32,
+}
+
+fn sup_dist((ax, ay): &(i32, i32), (bx, by): &(i32, i32)) -> i32 {
+    i32::max(i32::abs(ax - bx), i32::abs(ay - by))
+}
+
+fn move_point((px, py): &mut (i32, i32), dir: Direction) {
+    match dir {
+        Direction::Up => *py += 1,
+        Direction::Down => *py -= 1,
+        Direction::Left => *px -= 1,
+        Direction::Right => *px += 1,
+    }
+}
+
+fn new_pos(moved: &(i32, i32), next: &(i32, i32)) -> (i32, i32) {
+    let mut new_pos = *next;
+    if sup_dist(moved, next) > 1 {
+        if moved.0 > next.0 {
+            new_pos.0 += 1;
+        }
+        if moved.0 < next.0 {
+            new_pos.0 -= 1;
+        }
+        if moved.1 > next.1 {
+            new_pos.1 += 1;
+        }
+        if moved.1 < next.1 {
+            new_pos.1 -= 1;
+        }
+    }
+
+    new_pos
+}
+
+/// Number of positions the last knot of a rope with `knot_count` knots visits.
+pub fn count_visited_by_tail(motions: &[Motion], knot_count: usize) -> usize {
+    let mut visited = HashSet::<(i32, i32)>::new();
+    let mut knots = vec![(0i32, 0i32); knot_count];
+
+    visited.insert((0i32, 0i32));
+
+    for motion in motions {
+        for _ in 0..motion.distance {
+            move_point(&mut knots[0], motion.direction);
+            for i in 1..knot_count {
+                knots[i] = new_pos(&knots[i - 1], &knots[i]);
+            }
+
+            visited.insert(*(knots.last().expect("Pair is found")));
+        }
+    }
+
+    visited.len()
+}
+
+impl Solver for Day09 {
+    const DAY: u32 = 9;
+
+    type Input = Vec<Motion>;
+    type AnswerA = usize;
+    type AnswerB = usize;
+
+    fn parse(input: &str) -> Result<Vec<Motion>, ParseError> {
+        parse_lines(input, |line| {
+            let (dir, dist_str) = split_once(line, line, " ")?;
+
+            let direction = match dir {
+                "U" => Direction::Up,
+                "D" => Direction::Down,
+                "L" => Direction::Left,
+                "R" => Direction::Right,
+                _ => {
+                    return Err(LineError::at(
+                        line,
+                        dir,
+                        format!("expected one of U, D, L, R, found '{dir}'"),
+                    ))
+                }
+            };
+
+            Ok(Motion {
+                direction,
+                distance: number(line, dist_str)?,
+            })
+        })
+    }
+
+    fn part_a(input: &Vec<Motion>) -> Option<usize> {
+        Some(count_visited_by_tail(input, 2))
+    }
+
+    fn part_b(input: &Vec<Motion>) -> Option<usize> {
+        Some(count_visited_by_tail(input, 10))
+    }
+}
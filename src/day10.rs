@@ -0,0 +1,898 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use crate::shared::parse::{number, parse_lines, strip_prefix};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day10;
+
+/// Registers of the handheld device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Registers {
+    pub x: i32,
+}
+
+impl Registers {
+    pub const NAMES: [&'static str; 1] = ["x"];
+
+    pub fn get(&self, name: &str) -> Option<i32> {
+        match name {
+            "x" => Some(self.x),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Registers {
+    fn default() -> Registers {
+        Registers { x: 1 }
+    }
+}
+
+/// An instruction the CPU can run, an instruction set of another puzzle only needs to implement
+/// this to run on the same device.
+pub trait Operation {
+    /// Number of cycles the instruction takes, at least one. Its effect shows after the last.
+    fn cycles(&self) -> usize;
+
+    fn execute(&self, registers: &mut Registers);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl Operation for Instruction {
+    fn cycles(&self) -> usize {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+
+    fn execute(&self, registers: &mut Registers) {
+        match self {
+            Instruction::Noop => {}
+            Instruction::Addx(value) => registers.x += value,
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Noop => write!(f, "noop"),
+            Instruction::Addx(value) => write!(f, "addx {value}"),
+        }
+    }
+}
+
+/// Runs a program one cycle at a time. Cycles are numbered from 1 and the registers "during" a
+/// cycle are those before the instruction finishing in it takes effect.
+#[derive(Debug, Clone)]
+pub struct Cpu<'a, I> {
+    program: &'a [I],
+    registers: Registers,
+    /// Index of the instruction being executed
+    pc: usize,
+    /// Cycles completed so far
+    cycle: usize,
+    /// Cycles already spent on the instruction at `pc`
+    spent: usize,
+}
+
+impl<'a, I: Operation> Cpu<'a, I> {
+    pub fn new(program: &'a [I]) -> Cpu<'a, I> {
+        Cpu {
+            program,
+            registers: Registers::default(),
+            pc: 0,
+            cycle: 0,
+            spent: 0,
+        }
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    /// Number of cycles completed so far.
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// The instruction the next cycle works on.
+    pub fn current(&self) -> Option<&'a I> {
+        self.program.get(self.pc)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Runs one cycle and returns its number with the registers during it, `None` once the
+    /// program has ended.
+    pub fn step(&mut self) -> Option<(usize, Registers)> {
+        let instruction = self.current()?;
+        let during = self.registers;
+
+        self.cycle += 1;
+        self.spent += 1;
+        if self.spent >= instruction.cycles() {
+            instruction.execute(&mut self.registers);
+            self.pc += 1;
+            self.spent = 0;
+        }
+
+        Some((self.cycle, during))
+    }
+
+    /// Runs the program to its end, `during` is called in every cycle.
+    pub fn run(&mut self, mut during: impl FnMut(usize, &Registers)) {
+        while let Some((cycle, registers)) = self.step() {
+            during(cycle, &registers);
+        }
+    }
+}
+
+/// Value of the `x` register during every cycle of the program.
+pub fn cycle_values(program: &[Instruction]) -> Vec<i32> {
+    let mut cycle_values = Vec::<i32>::new();
+    Cpu::new(program).run(|_, registers| cycle_values.push(registers.x));
+
+    cycle_values
+}
+
+/// Arithmetic over registers, the number of the `cycle` and whole numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Number(i64),
+    Register(String),
+    Cycle,
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn eval(&self, cycle: usize, registers: &Registers) -> i64 {
+        match self {
+            Expr::Number(n) => *n,
+            Expr::Register(name) => registers.get(name).map_or(0, i64::from),
+            Expr::Cycle => cycle as i64,
+            Expr::Neg(e) => e.eval(cycle, registers).wrapping_neg(),
+            Expr::Add(l, r) => l
+                .eval(cycle, registers)
+                .wrapping_add(r.eval(cycle, registers)),
+            Expr::Sub(l, r) => l
+                .eval(cycle, registers)
+                .wrapping_sub(r.eval(cycle, registers)),
+            Expr::Mul(l, r) => l
+                .eval(cycle, registers)
+                .wrapping_mul(r.eval(cycle, registers)),
+        }
+    }
+}
+
+/// Recursive descent over the characters of an expression with whitespace removed.
+struct ExprParser<'s> {
+    s: &'s [u8],
+    at: usize,
+}
+
+impl ExprParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.at).copied()
+    }
+
+    fn sum(&mut self) -> Result<Expr, String> {
+        let mut e = self.product()?;
+        while let Some(op @ (b'+' | b'-')) = self.peek() {
+            self.at += 1;
+            let r = Box::new(self.product()?);
+            e = if op == b'+' {
+                Expr::Add(Box::new(e), r)
+            } else {
+                Expr::Sub(Box::new(e), r)
+            };
+        }
+
+        Ok(e)
+    }
+
+    fn product(&mut self) -> Result<Expr, String> {
+        let mut e = self.atom()?;
+        while self.peek() == Some(b'*') {
+            self.at += 1;
+            e = Expr::Mul(Box::new(e), Box::new(self.atom()?));
+        }
+
+        Ok(e)
+    }
+
+    fn atom(&mut self) -> Result<Expr, String> {
+        let start = self.at;
+        match self.peek() {
+            Some(b'-') => {
+                self.at += 1;
+                Ok(Expr::Neg(Box::new(self.atom()?)))
+            }
+            Some(b'(') => {
+                self.at += 1;
+                let e = self.sum()?;
+                if self.peek() != Some(b')') {
+                    return Err(format!("expected ')' at position {}", self.at + 1));
+                }
+                self.at += 1;
+                Ok(e)
+            }
+            Some(c) if c.is_ascii_digit() => {
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.at += 1;
+                }
+                let digits =
+                    std::str::from_utf8(&self.s[start..self.at]).expect("Digits are ASCII");
+                digits
+                    .parse()
+                    .map(Expr::Number)
+                    .map_err(|_| format!("number {digits} is too large"))
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                while self.peek().is_some_and(|c| c.is_ascii_alphanumeric()) {
+                    self.at += 1;
+                }
+                let name = std::str::from_utf8(&self.s[start..self.at]).expect("Names are ASCII");
+                if name == "cycle" {
+                    Ok(Expr::Cycle)
+                } else if Registers::NAMES.contains(&name) {
+                    Ok(Expr::Register(name.to_string()))
+                } else {
+                    Err(format!("unknown name '{name}'"))
+                }
+            }
+            _ => Err(format!(
+                "expected a number, a name or '(' at position {}",
+                self.at + 1
+            )),
+        }
+    }
+}
+
+impl FromStr for Expr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Expr, String> {
+        let compact = s.split_whitespace().collect::<String>();
+        let mut parser = ExprParser {
+            s: compact.as_bytes(),
+            at: 0,
+        };
+        let e = parser.sum()?;
+        match parser.peek() {
+            None => Ok(e),
+            Some(c) => Err(format!(
+                "unexpected '{}' at position {}",
+                c as char,
+                parser.at + 1
+            )),
+        }
+    }
+}
+
+/// An expression shown whenever the debugger stops.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Watch {
+    pub source: String,
+    pub expr: Expr,
+}
+
+impl FromStr for Watch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Watch, String> {
+        Ok(Watch {
+            source: s.trim().to_string(),
+            expr: s.parse()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Stops before the cycle runs, so the registers shown are those during it
+    Cycle(usize),
+    /// Stops once the register takes the value
+    Register(String, i32),
+}
+
+impl Breakpoint {
+    /// `next_cycle` is `None` once the program has ended.
+    fn hit(&self, next_cycle: Option<usize>, before: &Registers, after: &Registers) -> bool {
+        match self {
+            Breakpoint::Cycle(cycle) => next_cycle == Some(*cycle),
+            Breakpoint::Register(name, value) => {
+                before.get(name) != Some(*value) && after.get(name) == Some(*value)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Breakpoint::Cycle(cycle) => write!(f, "cycle {cycle}"),
+            Breakpoint::Register(name, value) => write!(f, "{name} == {value}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Step(usize),
+    Continue,
+    Break(Breakpoint),
+    Delete(usize),
+    Watch(Watch),
+    Unwatch(usize),
+    Info,
+    Help,
+    Quit,
+}
+
+pub const DEBUGGER_HELP: &str = "\
+step [n]           run one or n cycles (s)
+continue           run until a breakpoint or the end of the program (c)
+break cycle <n>    stop before cycle n runs (b)
+break <reg> <v>    stop once a register becomes v
+delete <n>         remove breakpoint n
+watch <expr>       show an expression of registers, cycle and numbers at every stop (w)
+unwatch <n>        remove watch n
+info               show the state, breakpoints and watches (i)
+quit               leave the debugger (q)";
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Command, String> {
+        let s = s.trim();
+        let (name, rest) = s.split_once(' ').unwrap_or((s, ""));
+        let rest = rest.trim();
+        let index = |what: &str| {
+            rest.parse::<usize>()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or(format!("expected the number of a {what}, found '{rest}'"))
+        };
+
+        match name {
+            "step" | "s" if rest.is_empty() => Ok(Command::Step(1)),
+            "step" | "s" => rest
+                .parse()
+                .map(Command::Step)
+                .map_err(|_| format!("expected a number of cycles, found '{rest}'")),
+            "continue" | "c" => Ok(Command::Continue),
+            "break" | "b" => match rest.split_whitespace().collect::<Vec<&str>>()[..] {
+                ["cycle", n] => n
+                    .parse()
+                    .map(|n| Command::Break(Breakpoint::Cycle(n)))
+                    .map_err(|_| format!("expected a cycle number, found '{n}'")),
+                [register, value] if Registers::NAMES.contains(&register) => value
+                    .parse()
+                    .map(|v| Command::Break(Breakpoint::Register(register.to_string(), v)))
+                    .map_err(|_| format!("expected a register value, found '{value}'")),
+                _ => Err(format!(
+                    "expected 'cycle <n>' or '<register> <value>' with a register among {}",
+                    Registers::NAMES.join(", ")
+                )),
+            },
+            "delete" => index("breakpoint").map(Command::Delete),
+            "watch" | "w" => rest.parse().map(Command::Watch),
+            "unwatch" => index("watch").map(Command::Unwatch),
+            "info" | "i" => Ok(Command::Info),
+            "help" | "h" => Ok(Command::Help),
+            "quit" | "q" => Ok(Command::Quit),
+            _ => Err(format!("unknown command '{name}', try 'help'")),
+        }
+    }
+}
+
+/// A CPU that can be stepped, stopped at breakpoints and inspected through watches.
+pub struct Debugger<'a, I> {
+    pub cpu: Cpu<'a, I>,
+    breakpoints: Vec<Breakpoint>,
+    watches: Vec<Watch>,
+    /// Whether `resume` already looked for a breakpoint on the first cycle
+    start_checked: bool,
+}
+
+impl<'a, I: Operation + fmt::Display> Debugger<'a, I> {
+    pub fn new(program: &'a [I]) -> Debugger<'a, I> {
+        Debugger {
+            cpu: Cpu::new(program),
+            breakpoints: Vec::new(),
+            watches: Vec::new(),
+            start_checked: false,
+        }
+    }
+
+    /// Runs up to `cycles` cycles, stops early at the end of the program.
+    pub fn step(&mut self, cycles: usize) {
+        for _ in 0..cycles {
+            if self.cpu.step().is_none() {
+                break;
+            }
+        }
+    }
+
+    /// Runs until a breakpoint is hit, which is returned, or the program ends. A breakpoint on
+    /// the first cycle stops it before anything runs, the first time only; otherwise it runs at
+    /// least one cycle so that it can leave the breakpoint it stopped at. A register breakpoint
+    /// hit by the last instruction is still reported.
+    pub fn resume(&mut self) -> Option<Breakpoint> {
+        if self.cpu.cycle() == 0 && !self.start_checked {
+            self.start_checked = true;
+            let first_cycle = (!self.cpu.is_halted()).then_some(1);
+            let registers = self.cpu.registers();
+            if let Some(breakpoint) = self
+                .breakpoints
+                .iter()
+                .find(|b| b.hit(first_cycle, registers, registers))
+            {
+                return Some(breakpoint.clone());
+            }
+        }
+
+        loop {
+            let before = *self.cpu.registers();
+            self.cpu.step()?;
+
+            let halted = self.cpu.is_halted();
+            let next_cycle = (!halted).then_some(self.cpu.cycle() + 1);
+            let after = self.cpu.registers();
+            if let Some(breakpoint) = self
+                .breakpoints
+                .iter()
+                .find(|b| b.hit(next_cycle, &before, after))
+            {
+                return Some(breakpoint.clone());
+            }
+            if halted {
+                return None;
+            }
+        }
+    }
+
+    /// Where the program stands and the values of the watches.
+    pub fn status(&self) -> String {
+        let registers = self.cpu.registers();
+        let mut lines = vec![match self.cpu.current() {
+            Some(instruction) => format!(
+                "cycle {}: x = {}, executing {} (instruction {})",
+                self.cpu.cycle() + 1,
+                registers.x,
+                instruction,
+                self.cpu.pc() + 1
+            ),
+            None => format!(
+                "halted after {} cycles: x = {}",
+                self.cpu.cycle(),
+                registers.x
+            ),
+        }];
+
+        lines.extend(self.watches.iter().map(|w| {
+            format!(
+                "  {} = {}",
+                w.source,
+                w.expr.eval(self.cpu.cycle() + 1, registers)
+            )
+        }));
+
+        lines.join("\n")
+    }
+
+    /// Carries out a command, returns what should be shown to the user.
+    pub fn execute(&mut self, command: Command) -> String {
+        match command {
+            Command::Step(cycles) => {
+                self.step(cycles);
+                self.status()
+            }
+            Command::Continue => match self.resume() {
+                Some(breakpoint) => format!("Breakpoint {breakpoint}\n{}", self.status()),
+                None => self.status(),
+            },
+            Command::Break(breakpoint) => {
+                self.breakpoints.push(breakpoint);
+                format!("Breakpoint {} set", self.breakpoints.len())
+            }
+            Command::Delete(n) if n <= self.breakpoints.len() => {
+                format!("Deleted breakpoint {}", self.breakpoints.remove(n - 1))
+            }
+            Command::Watch(watch) => {
+                self.watches.push(watch);
+                format!("Watch {} set", self.watches.len())
+            }
+            Command::Unwatch(n) if n <= self.watches.len() => {
+                format!("Deleted watch {}", self.watches.remove(n - 1).source)
+            }
+            Command::Delete(n) => format!("No breakpoint {n}"),
+            Command::Unwatch(n) => format!("No watch {n}"),
+            Command::Info => {
+                let mut lines = vec![self.status()];
+                lines.extend(
+                    self.breakpoints
+                        .iter()
+                        .enumerate()
+                        .map(|(i, b)| format!("breakpoint {}: {b}", i + 1)),
+                );
+                lines.extend(
+                    self.watches
+                        .iter()
+                        .enumerate()
+                        .map(|(i, w)| format!("watch {}: {}", i + 1, w.source)),
+                );
+                lines.join("\n")
+            }
+            Command::Help => DEBUGGER_HELP.to_string(),
+            Command::Quit => String::new(),
+        }
+    }
+
+    /// Reads commands from `input` one per line until `quit` or the end of the input, writing
+    /// the status, a prompt before every command and what it shows to `output`.
+    pub fn repl(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        writeln!(output, "{}", self.status())?;
+        let mut lines = input.lines();
+        loop {
+            write!(output, "(dbg) ")?;
+            output.flush()?;
+            let Some(line) = lines.next().transpose()? else {
+                break;
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match line.parse::<Command>() {
+                Ok(Command::Quit) => break,
+                Ok(command) => writeln!(output, "{}", self.execute(command))?,
+                Err(e) => writeln!(output, "{e}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub const CRT_WIDTH: usize = 40;
+pub const CRT_HEIGHT: usize = 6;
+
+/// The picture drawn by the program, `None` when it does not run long enough to fill the screen.
+pub fn render_crt(program: &[Instruction]) -> Option<String> {
+    let cycle_values = cycle_values(program);
+
+    let mut pixels = cycle_values.iter().enumerate().map(|(i, &x)| {
+        if (i % CRT_WIDTH).abs_diff(x as usize) <= 1 {
+            '#'
+        } else {
+            '.'
+        }
+    });
+
+    (0..CRT_HEIGHT)
+        .map(|_| {
+            (0..CRT_WIDTH)
+                .map(|_| pixels.next())
+                .collect::<Option<String>>()
+        })
+        .collect::<Option<Vec<String>>>()
+        .map(|rows| rows.join("\n"))
+}
+
+/// Letters of the font used on the CRT, 4 pixels wide and 6 tall, each followed by an unlit
+/// column. Only the letters known to appear in puzzles are included.
+const FONT: [(char, [&str; CRT_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const GLYPH_WIDTH: usize = 4;
+
+/// Glyphs of a CRT picture that are not letters of the font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnrecognisedGlyphs {
+    /// The text read with `?` in place of every unrecognised glyph
+    pub partial: String,
+    /// The unrecognised glyphs as their position in the text, counted from 1, and their pixels
+    pub glyphs: Vec<(usize, Vec<String>)>,
+}
+
+impl fmt::Display for UnrecognisedGlyphs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let positions = self
+            .glyphs
+            .iter()
+            .map(|(position, _)| position.to_string())
+            .collect::<Vec<String>>();
+        write!(
+            f,
+            "Read {}, unrecognised glyphs at positions {}",
+            self.partial,
+            positions.join(", ")
+        )
+    }
+}
+
+impl Error for UnrecognisedGlyphs {}
+
+/// Reads the letters of a CRT picture drawn with `#` and `.`.
+pub fn recognise(crt: &str) -> Result<String, UnrecognisedGlyphs> {
+    let rows = crt.lines().map(|l| l.as_bytes()).collect::<Vec<&[u8]>>();
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let glyph_count = width.div_ceil(GLYPH_WIDTH + 1);
+
+    let mut partial = String::new();
+    let mut unrecognised = Vec::new();
+
+    for i in 0..glyph_count {
+        let x = i * (GLYPH_WIDTH + 1);
+        let glyph = (0..rows.len().max(CRT_HEIGHT))
+            .map(|y| {
+                (x..x + GLYPH_WIDTH)
+                    .map(|xx| match rows.get(y).and_then(|r| r.get(xx)) {
+                        Some(b'#') => '#',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+
+        match FONT.iter().find(|(_, pixels)| glyph == pixels) {
+            Some(&(letter, _)) => partial.push(letter),
+            None => {
+                partial.push('?');
+                unrecognised.push((i + 1, glyph));
+            }
+        }
+    }
+
+    if unrecognised.is_empty() {
+        Ok(partial)
+    } else {
+        Err(UnrecognisedGlyphs {
+            partial,
+            glyphs: unrecognised,
+        })
+    }
+}
+
+impl Solver for Day10 {
+    const DAY: u32 = 10;
+
+    type Input = Vec<Instruction>;
+    type AnswerA = i32;
+    type AnswerB = String;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+        parse_lines(input, |line| {
+            if line == "noop" {
+                Ok(Instruction::Noop)
+            } else {
+                let value_str = strip_prefix(line, line, "addx ")?;
+
+                Ok(Instruction::Addx(number(line, value_str)?))
+            }
+        })
+    }
+
+    fn part_a(input: &Vec<Instruction>) -> Option<i32> {
+        let cycle_values = cycle_values(input);
+
+        [20, 60, 100, 140, 180, 220]
+            .iter()
+            .map(|&i| cycle_values.get(i - 1).map(|&x| i as i32 * x))
+            .sum::<Option<i32>>()
+    }
+
+    fn part_b(input: &Vec<Instruction>) -> Option<String> {
+        recognise(&render_crt(input)?).ok()
+    }
+
+    fn try_part_b(input: &Vec<Instruction>) -> Result<Option<String>, Box<dyn Error>> {
+        match render_crt(input) {
+            Some(crt) => Ok(Some(recognise(&crt)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: [Instruction; 3] = [
+        Instruction::Noop,
+        Instruction::Addx(3),
+        Instruction::Addx(-5),
+    ];
+
+    #[test]
+    fn resume_stops_at_breakpoints() {
+        let mut debugger = Debugger::new(&PROGRAM);
+        debugger.execute(Command::Break(Breakpoint::Cycle(3)));
+        debugger.execute(Command::Break(Breakpoint::Register("x".to_string(), 4)));
+
+        assert_eq!(debugger.resume(), Some(Breakpoint::Cycle(3)));
+        assert_eq!(
+            debugger.resume(),
+            Some(Breakpoint::Register("x".to_string(), 4))
+        );
+        assert_eq!(debugger.resume(), None);
+    }
+
+    #[test]
+    fn register_breakpoint_on_the_last_instruction_is_reported() {
+        let mut debugger = Debugger::new(&PROGRAM);
+        debugger.execute(Command::Break(Breakpoint::Register("x".to_string(), -1)));
+        debugger.execute(Command::Break(Breakpoint::Cycle(6)));
+
+        assert_eq!(
+            debugger.resume(),
+            Some(Breakpoint::Register("x".to_string(), -1))
+        );
+        assert_eq!(debugger.resume(), None);
+    }
+
+    #[test]
+    fn breakpoint_on_the_first_cycle_stops_before_running() {
+        let mut debugger = Debugger::new(&PROGRAM);
+        debugger.execute(Command::Break(Breakpoint::Cycle(1)));
+
+        assert_eq!(debugger.resume(), Some(Breakpoint::Cycle(1)));
+        assert_eq!(debugger.cpu.cycle(), 0);
+        assert_eq!(debugger.resume(), None);
+        assert_eq!(debugger.cpu.cycle(), 5);
+
+        let mut empty = Debugger::<Instruction>::new(&[]);
+        empty.execute(Command::Break(Breakpoint::Cycle(1)));
+        assert_eq!(empty.resume(), None);
+    }
+
+    /// Sets `x` once its cycles are over.
+    struct Set {
+        cycles: usize,
+        x: i32,
+    }
+
+    impl Operation for Set {
+        fn cycles(&self) -> usize {
+            self.cycles
+        }
+
+        fn execute(&self, registers: &mut Registers) {
+            registers.x = self.x;
+        }
+    }
+
+    #[test]
+    fn cpu_shows_the_value_during_every_cycle() {
+        let program = [
+            Set { cycles: 1, x: 5 },
+            Set { cycles: 3, x: -2 },
+            Set { cycles: 2, x: 7 },
+        ];
+        let mut cpu = Cpu::new(&program);
+        let mut during = Vec::new();
+        let mut pcs = Vec::new();
+        while let Some((cycle, registers)) = cpu.step() {
+            during.push((cycle, registers.x));
+            pcs.push(cpu.pc());
+        }
+
+        assert_eq!(
+            during,
+            vec![(1, 1), (2, 5), (3, 5), (4, 5), (5, -2), (6, -2)]
+        );
+        assert_eq!(pcs, vec![1, 1, 1, 2, 2, 3]);
+        assert_eq!((cpu.cycle(), cpu.registers().x), (6, 7));
+        assert!(cpu.is_halted());
+        assert_eq!(cpu.step(), None);
+        assert_eq!(cycle_values(&PROGRAM), vec![1, 1, 1, 4, 4]);
+    }
+
+    const CRT: &str = "\
+###..#....###...##..####.###...##..#....
+#..#.#....#..#.#..#.#....#..#.#..#.#....
+#..#.#....#..#.#..#.###..###..#....#....
+###..#....###..####.#....#..#.#....#....
+#....#....#....#..#.#....#..#.#..#.#....
+#....####.#....#..#.#....###...##..####.";
+
+    #[test]
+    fn recognise_reads_the_letters() {
+        assert_eq!(recognise(CRT), Ok("PLPAFBCL".to_string()));
+    }
+
+    #[test]
+    fn recognise_reports_unknown_glyphs() {
+        // The bar of the A is gone and the C gets a pixel too many
+        let crt = CRT
+            .replacen("###..#....###..####.", "###..#....###..#..#.", 1)
+            .replacen(
+                "#..#.#....#..#.#..#.###..###..#....",
+                "#..#.#....#..#.#..#.###..###..#.#..",
+                1,
+            );
+        let e = recognise(&crt).unwrap_err();
+
+        assert_eq!(e.partial, "PLP?FB?L");
+        assert_eq!(
+            e.glyphs.iter().map(|(at, _)| *at).collect::<Vec<usize>>(),
+            vec![4, 7]
+        );
+        assert_eq!(
+            e.glyphs[0].1,
+            vec![".##.", "#..#", "#..#", "#..#", "#..#", "#..#"]
+        );
+        assert_eq!(
+            e.to_string(),
+            "Read PLP?FB?L, unrecognised glyphs at positions 4, 7"
+        );
+    }
+
+    #[test]
+    fn part_b_reports_unknown_glyphs() {
+        let program = vec![Instruction::Noop; CRT_WIDTH * CRT_HEIGHT];
+
+        assert_eq!(Day10::part_b(&program), None);
+        assert!(Day10::try_part_b(&program).is_err());
+        assert_eq!(Day10::try_part_b(&program[1..].to_vec()).ok(), Some(None));
+    }
+
+    #[test]
+    fn repl_runs_commands_until_quit() {
+        let mut debugger = Debugger::new(&PROGRAM);
+        let mut output = Vec::new();
+        let input = "break x 4\n\nc\nbogus\nquit\nstep\n";
+
+        debugger.repl(input.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<&str>>();
+
+        assert_eq!(lines[0], "cycle 1: x = 1, executing noop (instruction 1)");
+        assert_eq!(lines[1], "(dbg) Breakpoint 1 set");
+        assert_eq!(lines[2], "(dbg) (dbg) Breakpoint x == 4");
+        assert_eq!(
+            lines[3],
+            "cycle 4: x = 4, executing addx -5 (instruction 3)"
+        );
+        assert!(lines[4].starts_with("(dbg) "));
+        assert_eq!(lines[5], "(dbg) ");
+        assert_eq!(lines.len(), 6);
+    }
+}
@@ -0,0 +1,1092 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use crate::shared::cli::{Cli, Options};
+use crate::shared::parse::{number, strip_prefix, LineError};
+use crate::shared::runner::{parse_input, Part};
+use crate::shared::{BigUint, ParseError, Solver};
+
+pub struct Day11;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorryError {
+    Overflow,
+    Negative,
+    DivisionByZero,
+    /// The worry type cannot carry out the operation
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for WorryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorryError::Overflow => write!(f, "the worry level overflowed"),
+            WorryError::Negative => write!(f, "the worry level became negative"),
+            WorryError::DivisionByZero => write!(f, "the worry level was divided by zero"),
+            WorryError::Unsupported(what) => write!(f, "{what} is not supported by the worry type"),
+        }
+    }
+}
+
+impl Error for WorryError {}
+
+/// A worry level, the simulation is generic over how it is represented.
+pub trait Worry: Sized + Clone + fmt::Display {
+    /// `divisors` are the divisors of all the monkeys, for types tracking worry levels modulo
+    /// them.
+    fn new(value: u64, divisors: &[u64]) -> Self;
+
+    fn add(&self, other: &Self) -> Result<Self, WorryError>;
+
+    fn sub(&self, other: &Self) -> Result<Self, WorryError>;
+
+    fn mul(&self, other: &Self) -> Result<Self, WorryError>;
+
+    /// Division rounding down.
+    fn div(&self, other: &Self) -> Result<Self, WorryError>;
+
+    fn rem(&self, other: &Self) -> Result<Self, WorryError>;
+
+    /// Division rounding down, as relief does.
+    fn div_u64(&self, divisor: u64) -> Result<Self, WorryError>;
+
+    fn is_divisible_by(&self, divisor: u64) -> Result<bool, WorryError>;
+}
+
+/// Worry level in a `u64` that reports overflow instead of wrapping around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckedU64(pub u64);
+
+impl fmt::Display for CheckedU64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Worry for CheckedU64 {
+    fn new(value: u64, _divisors: &[u64]) -> CheckedU64 {
+        CheckedU64(value)
+    }
+
+    fn add(&self, other: &CheckedU64) -> Result<CheckedU64, WorryError> {
+        self.0
+            .checked_add(other.0)
+            .map(CheckedU64)
+            .ok_or(WorryError::Overflow)
+    }
+
+    fn sub(&self, other: &CheckedU64) -> Result<CheckedU64, WorryError> {
+        self.0
+            .checked_sub(other.0)
+            .map(CheckedU64)
+            .ok_or(WorryError::Negative)
+    }
+
+    fn mul(&self, other: &CheckedU64) -> Result<CheckedU64, WorryError> {
+        self.0
+            .checked_mul(other.0)
+            .map(CheckedU64)
+            .ok_or(WorryError::Overflow)
+    }
+
+    fn div(&self, other: &CheckedU64) -> Result<CheckedU64, WorryError> {
+        self.div_u64(other.0)
+    }
+
+    fn rem(&self, other: &CheckedU64) -> Result<CheckedU64, WorryError> {
+        self.0
+            .checked_rem(other.0)
+            .map(CheckedU64)
+            .ok_or(WorryError::DivisionByZero)
+    }
+
+    fn div_u64(&self, divisor: u64) -> Result<CheckedU64, WorryError> {
+        self.0
+            .checked_div(divisor)
+            .map(CheckedU64)
+            .ok_or(WorryError::DivisionByZero)
+    }
+
+    fn is_divisible_by(&self, divisor: u64) -> Result<bool, WorryError> {
+        self.0
+            .checked_rem(divisor)
+            .map(|r| r == 0)
+            .ok_or(WorryError::DivisionByZero)
+    }
+}
+
+/// Worry level kept only as its residues modulo every monkey's divisor. Never overflows, but
+/// cannot be divided, so it only works without relief and division in operations, and it cannot
+/// tell when a subtraction makes the worry level negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Residues {
+    /// Pairs of a modulus and the residue modulo it
+    residues: Vec<(u64, u64)>,
+}
+
+impl Residues {
+    fn combine(&self, other: &Residues, op: impl Fn(u128, u128) -> u128) -> Residues {
+        let residues = self
+            .residues
+            .iter()
+            .zip(&other.residues)
+            .map(|(&(m, a), &(_, b))| (m, (op(a as u128, b as u128) % m as u128) as u64))
+            .collect();
+
+        Residues { residues }
+    }
+}
+
+impl Residues {
+    /// The smallest worry level with these residues, found with the Chinese remainder theorem.
+    /// `None` when the least common multiple of the moduli does not fit into a `u128`.
+    pub fn value(&self) -> Option<u128> {
+        // Inverse of `a` modulo `m` by the extended Euclidean algorithm, `a` and `m` are coprime
+        fn inverse(a: i128, m: i128) -> i128 {
+            let (mut old_r, mut r) = (a, m);
+            let (mut old_s, mut s) = (1i128, 0i128);
+            while r != 0 {
+                let q = old_r / r;
+                (old_r, r) = (r, old_r - q * r);
+                (old_s, s) = (s, old_s - q * s);
+            }
+            old_s.rem_euclid(m)
+        }
+
+        let (mut value, mut modulus) = (0u128, 1u128);
+        for &(m, r) in &self.residues {
+            let m = m as u128;
+            let g = gcd::binary_u128(modulus, m);
+            let step = m / g;
+            let difference = (r as u128 + m - value % m) % m / g;
+            let t = difference * inverse((modulus / g % step) as i128, step as i128) as u128 % step;
+            // The new value stays below the new modulus, so it fits whenever the modulus does
+            let next_modulus = modulus.checked_mul(step)?;
+            value += modulus * t;
+            modulus = next_modulus;
+        }
+
+        Some(value)
+    }
+}
+
+/// The worry level when it can be recovered, otherwise its residues.
+impl fmt::Display for Residues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value() {
+            Some(value) => write!(f, "{value}"),
+            None => {
+                let residues = self
+                    .residues
+                    .iter()
+                    .map(|(m, r)| format!("{r} mod {m}"))
+                    .collect::<Vec<String>>();
+                write!(f, "({})", residues.join(", "))
+            }
+        }
+    }
+}
+
+impl Worry for Residues {
+    fn new(value: u64, divisors: &[u64]) -> Residues {
+        let residues = divisors
+            .iter()
+            .filter(|&&m| m > 0)
+            .unique()
+            .map(|&m| (m, value % m))
+            .collect();
+
+        Residues { residues }
+    }
+
+    fn add(&self, other: &Residues) -> Result<Residues, WorryError> {
+        Ok(self.combine(other, |a, b| a + b))
+    }
+
+    fn sub(&self, other: &Residues) -> Result<Residues, WorryError> {
+        let residues = self
+            .residues
+            .iter()
+            .zip(&other.residues)
+            .map(|(&(m, a), &(_, b))| (m, if a >= b { a - b } else { m - (b - a) }))
+            .collect();
+
+        Ok(Residues { residues })
+    }
+
+    fn mul(&self, other: &Residues) -> Result<Residues, WorryError> {
+        Ok(self.combine(other, |a, b| a * b))
+    }
+
+    fn div(&self, _other: &Residues) -> Result<Residues, WorryError> {
+        Err(WorryError::Unsupported("division"))
+    }
+
+    fn rem(&self, _other: &Residues) -> Result<Residues, WorryError> {
+        Err(WorryError::Unsupported("remainder"))
+    }
+
+    fn div_u64(&self, divisor: u64) -> Result<Residues, WorryError> {
+        match divisor {
+            0 => Err(WorryError::DivisionByZero),
+            1 => Ok(self.clone()),
+            _ => Err(WorryError::Unsupported("division")),
+        }
+    }
+
+    fn is_divisible_by(&self, divisor: u64) -> Result<bool, WorryError> {
+        self.residues
+            .iter()
+            .find(|&&(m, _)| m == divisor)
+            .map(|&(_, r)| r == 0)
+            .ok_or(WorryError::Unsupported(
+                "divisibility by an untracked divisor",
+            ))
+    }
+}
+
+/// Exact worry level of any size, which grows quickly without relief.
+impl Worry for BigUint {
+    fn new(value: u64, _divisors: &[u64]) -> BigUint {
+        BigUint::from(value)
+    }
+
+    fn add(&self, other: &BigUint) -> Result<BigUint, WorryError> {
+        Ok(self + other)
+    }
+
+    fn sub(&self, other: &BigUint) -> Result<BigUint, WorryError> {
+        self.checked_sub(other).ok_or(WorryError::Negative)
+    }
+
+    fn mul(&self, other: &BigUint) -> Result<BigUint, WorryError> {
+        Ok(self * other)
+    }
+
+    fn div(&self, other: &BigUint) -> Result<BigUint, WorryError> {
+        self.div_rem(other)
+            .map(|(quotient, _)| quotient)
+            .ok_or(WorryError::DivisionByZero)
+    }
+
+    fn rem(&self, other: &BigUint) -> Result<BigUint, WorryError> {
+        self.div_rem(other)
+            .map(|(_, remainder)| remainder)
+            .ok_or(WorryError::DivisionByZero)
+    }
+
+    fn div_u64(&self, divisor: u64) -> Result<BigUint, WorryError> {
+        self.div_rem_u64(divisor)
+            .map(|(quotient, _)| quotient)
+            .ok_or(WorryError::DivisionByZero)
+    }
+
+    fn is_divisible_by(&self, divisor: u64) -> Result<bool, WorryError> {
+        self.div_rem_u64(divisor)
+            .map(|(_, remainder)| remainder == 0)
+            .ok_or(WorryError::DivisionByZero)
+    }
+}
+
+/// The worry types available to the binaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorryKind {
+    Checked,
+    Residues,
+    Big,
+}
+
+impl FromStr for WorryKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<WorryKind, String> {
+        match s {
+            "checked" => Ok(WorryKind::Checked),
+            "residues" => Ok(WorryKind::Residues),
+            "big" => Ok(WorryKind::Big),
+            _ => Err(format!(
+                "Unknown worry type '{s}', expected checked, residues or big"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinOp {
+    fn symbol(&self) -> char {
+        match self {
+            BinOp::Add => '+',
+            BinOp::Sub => '-',
+            BinOp::Mul => '*',
+            BinOp::Div => '/',
+            BinOp::Rem => '%',
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            BinOp::Add | BinOp::Sub => 1,
+            BinOp::Mul | BinOp::Div | BinOp::Rem => 2,
+        }
+    }
+}
+
+/// The operation of a monkey computing the new worry level from the `old` one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Old,
+    Number(u64),
+    Binary(Box<Expr>, BinOp, Box<Expr>),
+}
+
+impl Expr {
+    pub fn eval<W: Worry>(&self, old: &W, divisors: &[u64]) -> Result<W, WorryError> {
+        match self {
+            Expr::Old => Ok(old.clone()),
+            Expr::Number(n) => Ok(W::new(*n, divisors)),
+            Expr::Binary(l, op, r) => {
+                let l = l.eval(old, divisors)?;
+                let r = r.eval(old, divisors)?;
+                match op {
+                    BinOp::Add => l.add(&r),
+                    BinOp::Sub => l.sub(&r),
+                    BinOp::Mul => l.mul(&r),
+                    BinOp::Div => l.div(&r),
+                    BinOp::Rem => l.rem(&r),
+                }
+            }
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Binary(_, op, _) => op.precedence(),
+            _ => u8::MAX,
+        }
+    }
+}
+
+/// Prints the expression the way the input writes it, with only the parentheses needed to keep
+/// its structure. Operators group to the left, so a right operand of the same precedence keeps
+/// its parentheses.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Old => write!(f, "old"),
+            Expr::Number(n) => write!(f, "{n}"),
+            Expr::Binary(l, op, r) => {
+                if l.precedence() < op.precedence() {
+                    write!(f, "({l})")?;
+                } else {
+                    write!(f, "{l}")?;
+                }
+                write!(f, " {} ", op.symbol())?;
+                if r.precedence() <= op.precedence() {
+                    write!(f, "({r})")
+                } else {
+                    write!(f, "{r}")
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    pub items: Vec<u64>,
+    pub divisor: u64,
+    pub operation: Expr,
+    pub true_target: usize,
+    pub false_target: usize,
+}
+
+/// Recursive descent over an operation, `rest` is the part of `line` not parsed yet.
+struct OperationParser<'a> {
+    line: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> OperationParser<'a> {
+    fn error(&self, message: impl Into<String>) -> LineError {
+        LineError::at(self.line, self.rest, message)
+    }
+
+    /// Consumes the next character when it is one of `symbols`.
+    fn symbol(&mut self, symbols: &str) -> Option<char> {
+        self.rest = self.rest.trim_start();
+        let c = self.rest.chars().next().filter(|c| symbols.contains(*c))?;
+        self.rest = &self.rest[1..];
+
+        Some(c)
+    }
+
+    fn binary(
+        &mut self,
+        symbols: &str,
+        mut operand: impl FnMut(&mut Self) -> Result<Expr, LineError>,
+    ) -> Result<Expr, LineError> {
+        let mut e = operand(self)?;
+        while let Some(c) = self.symbol(symbols) {
+            let op = match c {
+                '+' => BinOp::Add,
+                '-' => BinOp::Sub,
+                '*' => BinOp::Mul,
+                '/' => BinOp::Div,
+                _ => BinOp::Rem,
+            };
+            e = Expr::Binary(Box::new(e), op, Box::new(operand(self)?));
+        }
+
+        Ok(e)
+    }
+
+    fn sum(&mut self) -> Result<Expr, LineError> {
+        self.binary("+-", Self::product)
+    }
+
+    fn product(&mut self) -> Result<Expr, LineError> {
+        self.binary("*/%", Self::operand)
+    }
+
+    fn operand(&mut self) -> Result<Expr, LineError> {
+        if self.symbol("(").is_some() {
+            let e = self.sum()?;
+            return match self.symbol(")") {
+                Some(_) => Ok(e),
+                None => Err(self.error("expected ')'")),
+            };
+        }
+
+        if let Some(rest) = self.rest.strip_prefix("old") {
+            self.rest = rest;
+            return Ok(Expr::Old);
+        }
+
+        let digits = self
+            .rest
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(self.rest, |end| &self.rest[..end]);
+        if digits.is_empty() {
+            return Err(self.error("expected 'old', a number or '('"));
+        }
+        let n = number::<u64>(self.line, digits)?;
+        self.rest = &self.rest[digits.len()..];
+
+        Ok(Expr::Number(n))
+    }
+}
+
+/// Parses `s`, the part of `line` after `new =`.
+fn parse_operation(line: &str, s: &str) -> Result<Expr, LineError> {
+    let mut parser = OperationParser { line, rest: s };
+    let e = parser.sum()?;
+
+    parser.rest = parser.rest.trim_start();
+    match parser.rest.chars().next() {
+        None => Ok(e),
+        Some(c) => Err(parser.error(format!("expected an operator, found '{c}'"))),
+    }
+}
+
+/// Lines of the notes on one monkey, each with its index in the input.
+type Notes<'a> = [(usize, &'a str)];
+
+/// The `k`-th line of the notes with the indentation and `prefix` removed, along with its line
+/// number.
+fn note<'a>(
+    notes: &Notes<'a>,
+    k: usize,
+    prefix: &str,
+) -> Result<(usize, &'a str, &'a str), ParseError> {
+    let &(i, line) = match notes.get(k) {
+        Some(note) => note,
+        None => {
+            let &(last, _) = notes.last().expect("Notes are not empty");
+            return Err(ParseError::new(
+                last + 2,
+                1,
+                "",
+                format!("expected '{}'", prefix.trim_end()),
+            ));
+        }
+    };
+
+    let rest = strip_prefix(line, line.trim_start(), prefix).map_err(|e| e.on_line(i + 1, line))?;
+
+    Ok((i + 1, line, rest))
+}
+
+fn parse_monkey(notes: &Notes, monkey_count: usize) -> Result<Monkey, ParseError> {
+    note(notes, 0, "Monkey ")?;
+
+    let (n, line, item_list_part) = note(notes, 1, "Starting items:")?;
+    let item_list_part = item_list_part.trim();
+    let items = if item_list_part.is_empty() {
+        Vec::new()
+    } else {
+        item_list_part
+            .split(',')
+            .map(|part| number::<u64>(line, part.trim()))
+            .collect::<Result<Vec<u64>, LineError>>()
+            .map_err(|e| e.on_line(n, line))?
+    };
+
+    let (n, line, op_part) = note(notes, 2, "Operation: new = ")?;
+    let operation = parse_operation(line, op_part).map_err(|e| e.on_line(n, line))?;
+
+    let (n, line, divisor_part) = note(notes, 3, "Test: divisible by ")?;
+    let divisor = number::<u64>(line, divisor_part).map_err(|e| e.on_line(n, line))?;
+    if divisor == 0 {
+        return Err(
+            LineError::at(line, divisor_part, "expected a positive divisor").on_line(n, line),
+        );
+    }
+
+    let target = |k: usize, prefix: &str| {
+        let (n, line, target_part) = note(notes, k, prefix)?;
+        match number::<usize>(line, target_part) {
+            Ok(target) if target < monkey_count => Ok(target),
+            Ok(_) => Err(LineError::at(
+                line,
+                target_part,
+                format!("expected a monkey below {monkey_count}"),
+            )
+            .on_line(n, line)),
+            Err(e) => Err(e.on_line(n, line)),
+        }
+    };
+
+    Ok(Monkey {
+        items,
+        operation,
+        divisor,
+        true_target: target(4, "If true: throw to monkey ")?,
+        false_target: target(5, "If false: throw to monkey ")?,
+    })
+}
+
+/// A worry operation that failed during the simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationError {
+    pub round: usize,
+    pub monkey: usize,
+    pub error: WorryError,
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "In round {} monkey {} failed: {}",
+            self.round, self.monkey, self.error
+        )
+    }
+}
+
+impl Error for SimulationError {}
+
+pub const PART_A_ROUNDS: usize = 20;
+pub const PART_A_RELIEF: u64 = 3;
+pub const PART_B_ROUNDS: usize = 10_000;
+
+/// Sees the items every monkey holds at the end of each round.
+pub trait RoundObserver {
+    fn after_round<W: Worry>(&mut self, round: usize, items: &[Vec<W>]);
+}
+
+/// No observer.
+impl RoundObserver for () {
+    fn after_round<W: Worry>(&mut self, _round: usize, _items: &[Vec<W>]) {}
+}
+
+/// Inspection counts of all monkeys after `rounds` rounds, every worry level is divided by
+/// `relief` after the monkey's operation.
+pub fn simulate<W: Worry>(
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief: u64,
+    observer: &mut impl RoundObserver,
+) -> Result<Vec<usize>, SimulationError> {
+    let divisors = monkeys.iter().map(|m| m.divisor).collect::<Vec<u64>>();
+    let mut items = monkeys
+        .iter()
+        .map(|m| m.items.iter().map(|&w| W::new(w, &divisors)).collect())
+        .collect::<Vec<Vec<W>>>();
+    let mut counts = vec![0usize; monkeys.len()];
+
+    for round in 1..=rounds {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            counts[i] += items[i].len();
+            let thrown = std::mem::take(&mut items[i]);
+
+            for item in thrown {
+                let failed = |error| SimulationError {
+                    round,
+                    monkey: i,
+                    error,
+                };
+
+                let mut worry = monkey.operation.eval(&item, &divisors).map_err(failed)?;
+                if relief != 1 {
+                    worry = worry.div_u64(relief).map_err(failed)?;
+                }
+                let target = if worry.is_divisible_by(monkey.divisor).map_err(failed)? {
+                    monkey.true_target
+                } else {
+                    monkey.false_target
+                };
+
+                items[target].push(worry);
+            }
+        }
+
+        observer.after_round(round, &items);
+    }
+
+    Ok(counts)
+}
+
+/// `simulate` with the worry type chosen at runtime.
+pub fn simulate_as(
+    kind: WorryKind,
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief: u64,
+    observer: &mut impl RoundObserver,
+) -> Result<Vec<usize>, SimulationError> {
+    match kind {
+        WorryKind::Checked => simulate::<CheckedU64>(monkeys, rounds, relief, observer),
+        WorryKind::Residues => simulate::<Residues>(monkeys, rounds, relief, observer),
+        WorryKind::Big => simulate::<BigUint>(monkeys, rounds, relief, observer),
+    }
+}
+
+/// Product of the two highest inspection counts, `None` with fewer than two monkeys.
+pub fn business(counts: &[usize]) -> Option<usize> {
+    counts
+        .iter()
+        .sorted()
+        .rev()
+        .take(2)
+        .collect_tuple()
+        .map(|(max, next_to_max)| max * next_to_max)
+}
+
+/// Monkey business after `rounds` rounds with worry levels divided by `relief`.
+pub fn monkey_business<W: Worry>(
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief: u64,
+) -> Result<Option<usize>, SimulationError> {
+    Ok(business(&simulate::<W>(monkeys, rounds, relief, &mut ())?))
+}
+
+/// The items held after a round, worded like the puzzle does.
+pub fn holdings<W: fmt::Display>(round: usize, items: &[Vec<W>]) -> String {
+    let mut lines = vec![format!(
+        "After round {round}, the monkeys are holding items with these worry levels:"
+    )];
+    lines.extend(items.iter().enumerate().map(|(i, held)| {
+        format!("Monkey {i}: {}", held.iter().join(", "))
+            .trim_end()
+            .to_string()
+    }));
+
+    lines.join("\n")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ReportFormat, String> {
+        match s {
+            "csv" => Ok(ReportFormat::Csv),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(format!("Unknown report format '{s}', expected csv or json")),
+        }
+    }
+}
+
+/// Inspection counts per monkey.
+pub fn inspection_report(counts: &[usize], format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Csv => std::iter::once("monkey,inspections".to_string())
+            .chain(counts.iter().enumerate().map(|(i, n)| format!("{i},{n}")))
+            .join("\n"),
+        ReportFormat::Json => {
+            let entries = counts
+                .iter()
+                .enumerate()
+                .map(|(i, n)| format!("  {{\"monkey\": {i}, \"inspections\": {n}}}"))
+                .join(",\n");
+            format!("[\n{entries}\n]")
+        }
+    }
+}
+
+/// Writes the items held after every round when it has an output, keeps the first write error.
+struct Trace<'a> {
+    output: Option<&'a mut dyn Write>,
+    result: io::Result<()>,
+}
+
+impl RoundObserver for Trace<'_> {
+    fn after_round<W: Worry>(&mut self, round: usize, items: &[Vec<W>]) {
+        if let (Some(output), Ok(())) = (&mut self.output, &self.result) {
+            self.result = writeln!(output, "{}\n", holdings(round, items));
+        }
+    }
+}
+
+/// `--rounds <n>` and `--relief <n>` change the number of rounds and what worry levels are
+/// divided by, `--worry <checked|residues|big>` picks how worry levels are represented. `--trace`
+/// prints the items held after every round and `--report <csv|json>` the inspection counts below
+/// the answer. Everything not given is as in the part.
+pub const CLI: Cli = Cli {
+    flags: &["--trace"],
+    valued: &["--worry", "--rounds", "--relief", "--report"],
+    run,
+};
+
+fn run(
+    part: Part,
+    options: &Options,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let monkeys = parse_input::<Day11>(input)?;
+    let (kind, rounds, relief) = match part {
+        Part::A => (WorryKind::Checked, PART_A_ROUNDS, PART_A_RELIEF),
+        Part::B => (WorryKind::Residues, PART_B_ROUNDS, 1),
+    };
+    let kind = options
+        .value("--worry")
+        .map_or(Ok(kind), str::parse::<WorryKind>)?;
+    let rounds = options.parsed::<usize>("--rounds")?.unwrap_or(rounds);
+    let relief = options.parsed::<u64>("--relief")?.unwrap_or(relief);
+    if relief == 0 {
+        return Err("The relief must be positive".into());
+    }
+    let format = options
+        .value("--report")
+        .map(str::parse::<ReportFormat>)
+        .transpose()?;
+
+    let mut trace = Trace {
+        output: options.flag("--trace").then_some(&mut *output),
+        result: Ok(()),
+    };
+    let counts = simulate_as(kind, &monkeys, rounds, relief, &mut trace)?;
+    trace.result?;
+    let answer = business(&counts).ok_or("The input has no answer")?;
+    writeln!(output, "{answer}")?;
+
+    if let Some(format) = format {
+        writeln!(output, "{}", inspection_report(&counts, format))?;
+    }
+
+    Ok(())
+}
+
+impl Solver for Day11 {
+    const DAY: u32 = 11;
+
+    type Input = Vec<Monkey>;
+    type AnswerA = usize;
+    type AnswerB = usize;
+
+    fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
+        let lines = input.lines().enumerate().collect::<Vec<(usize, &str)>>();
+        let notes = lines
+            .split(|(_, line)| line.is_empty())
+            .filter(|notes| !notes.is_empty())
+            .collect::<Vec<&Notes>>();
+
+        notes
+            .iter()
+            .map(|notes_on_monkey| parse_monkey(notes_on_monkey, notes.len()))
+            .collect()
+    }
+
+    fn part_a(monkeys: &Vec<Monkey>) -> Option<usize> {
+        monkey_business::<CheckedU64>(monkeys, PART_A_ROUNDS, PART_A_RELIEF).ok()?
+    }
+
+    fn part_b(monkeys: &Vec<Monkey>) -> Option<usize> {
+        monkey_business::<Residues>(monkeys, PART_B_ROUNDS, 1).ok()?
+    }
+
+    fn try_part_a(monkeys: &Vec<Monkey>) -> Result<Option<usize>, Box<dyn Error>> {
+        Ok(monkey_business::<CheckedU64>(
+            monkeys,
+            PART_A_ROUNDS,
+            PART_A_RELIEF,
+        )?)
+    }
+
+    fn try_part_b(monkeys: &Vec<Monkey>) -> Result<Option<usize>, Box<dyn Error>> {
+        Ok(monkey_business::<Residues>(monkeys, PART_B_ROUNDS, 1)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::random::Random;
+
+    const EXAMPLE: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    fn operation(s: &str) -> Result<Expr, LineError> {
+        parse_operation(s, s)
+    }
+
+    fn binary(l: Expr, op: BinOp, r: Expr) -> Expr {
+        Expr::Binary(Box::new(l), op, Box::new(r))
+    }
+
+    fn random_expr(random: &mut Random, depth: usize) -> Expr {
+        const OPS: [BinOp; 5] = [BinOp::Add, BinOp::Sub, BinOp::Mul, BinOp::Div, BinOp::Rem];
+
+        match random.below(if depth == 0 { 2 } else { 4 }) {
+            0 => Expr::Old,
+            1 => Expr::Number(random.next() >> random.below(64)),
+            _ => binary(
+                random_expr(random, depth - 1),
+                OPS[random.below(OPS.len())],
+                random_expr(random, depth - 1),
+            ),
+        }
+    }
+
+    #[test]
+    fn example() {
+        let monkeys = Day11::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day11::part_a(&monkeys), Some(10605));
+        assert_eq!(Day11::part_b(&monkeys), Some(2713310158));
+    }
+
+    #[test]
+    fn operations_follow_precedence() {
+        assert_eq!(
+            operation("old + 2 * old"),
+            Ok(binary(
+                Expr::Old,
+                BinOp::Add,
+                binary(Expr::Number(2), BinOp::Mul, Expr::Old)
+            ))
+        );
+        assert_eq!(
+            operation("(old + 2) * old"),
+            Ok(binary(
+                binary(Expr::Old, BinOp::Add, Expr::Number(2)),
+                BinOp::Mul,
+                Expr::Old
+            ))
+        );
+        assert_eq!(
+            operation("10 - old - 1"),
+            Ok(binary(
+                binary(Expr::Number(10), BinOp::Sub, Expr::Old),
+                BinOp::Sub,
+                Expr::Number(1)
+            ))
+        );
+        assert_eq!(
+            operation(" 7 %(old/ 2) "),
+            Ok(binary(
+                Expr::Number(7),
+                BinOp::Rem,
+                binary(Expr::Old, BinOp::Div, Expr::Number(2))
+            ))
+        );
+
+        let e = operation("3 * old - old % 4").unwrap();
+        assert_eq!(e.eval(&CheckedU64(5), &[]), Ok(CheckedU64(14)));
+        assert_eq!(
+            operation("old - old * 2")
+                .unwrap()
+                .eval(&CheckedU64(5), &[]),
+            Err(WorryError::Negative)
+        );
+    }
+
+    #[test]
+    fn operations_print_back_with_only_the_needed_parentheses() {
+        for (source, printed) in [
+            ("old * 19", "old * 19"),
+            ("((old))", "old"),
+            ("(old * old) + 3", "old * old + 3"),
+            ("old * (old + 3)", "old * (old + 3)"),
+            ("(old - 1) - (old - 2)", "old - 1 - (old - 2)"),
+            ("old / (old * 2)", "old / (old * 2)"),
+            ("(old / old) * 2", "old / old * 2"),
+        ] {
+            assert_eq!(operation(source).unwrap().to_string(), printed);
+        }
+    }
+
+    #[test]
+    fn printed_operations_parse_back() {
+        let mut random = Random(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..2000 {
+            let e = random_expr(&mut random, 4);
+            let printed = e.to_string();
+
+            assert_eq!(operation(&printed), Ok(e), "{printed}");
+        }
+    }
+
+    #[test]
+    fn operation_errors_point_at_the_column() {
+        let error = |op: &str| {
+            let line = format!("  Operation: new = {op}");
+            let e = parse_operation(&line, &line[19..]).unwrap_err();
+            (e.column, e.message)
+        };
+
+        assert_eq!(
+            error("old *"),
+            (25, "expected 'old', a number or '('".to_string())
+        );
+        assert_eq!(error("(old + 1"), (28, "expected ')'".to_string()));
+        assert_eq!(
+            error("old old"),
+            (24, "expected an operator, found 'o'".to_string())
+        );
+        assert_eq!(
+            error("old + x"),
+            (26, "expected 'old', a number or '('".to_string())
+        );
+        assert_eq!(
+            error("old * 99999999999999999999"),
+            (
+                26,
+                "expected a number, found '99999999999999999999'".to_string()
+            )
+        );
+
+        let e = Day11::parse(&EXAMPLE.replace("old * old", "old ^ old")).unwrap_err();
+        assert_eq!((e.line, e.column), (17, 24));
+    }
+
+    fn run_with(part: Part, args: &[&str]) -> String {
+        CLI.run_on(part, args, EXAMPLE).unwrap()
+    }
+
+    #[test]
+    fn options_default_to_the_part() {
+        assert_eq!(
+            run_with(Part::A, &["--report", "csv"]).lines().next(),
+            Some("10605")
+        );
+        assert_eq!(
+            run_with(
+                Part::B,
+                &["--rounds", "20", "--relief", "3", "--worry", "checked"]
+            ),
+            "10605\n"
+        );
+        assert_eq!(
+            run_with(Part::B, &["--rounds", "1000", "--report", "csv"]),
+            "27019168\nmonkey,inspections\n0,5204\n1,4792\n2,199\n3,5192\n"
+        );
+
+        let trace = run_with(Part::A, &["--trace", "--rounds", "1"]);
+        assert!(trace.starts_with("After round 1, the monkeys are holding"));
+        assert!(trace.ends_with("\n\n20\n"));
+    }
+
+    #[test]
+    fn residues_recover_the_value() {
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..1000 {
+            let divisors = (0..1 + random.below(5))
+                .map(|_| 1 + random.below(30) as u64)
+                .collect::<Vec<u64>>();
+            let lcm = divisors
+                .iter()
+                .fold(1, |lcm, &d| lcm / gcd::binary_u64(lcm, d) * d);
+            let value = random.next() % 100_000;
+
+            let residues = Residues::new(value, &divisors);
+            assert_eq!(
+                residues.value(),
+                Some((value % lcm) as u128),
+                "{divisors:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn residues_of_large_moduli_do_not_overflow() {
+        let two = [u64::MAX, u64::MAX - 1];
+        let residues = Residues::new(u64::MAX - 5, &two);
+        assert_eq!(residues.value(), Some(u64::MAX as u128 - 5));
+
+        // The cube needs all three moduli, whose product does not fit
+        let three = [u64::MAX, u64::MAX - 1, u64::MAX - 2];
+        let residues = Residues::new(u64::MAX - 5, &three);
+        let cube = residues.mul(&residues).unwrap().mul(&residues).unwrap();
+        assert_eq!(cube.value(), None);
+        assert_eq!(
+            cube.to_string(),
+            format!(
+                "({} mod {}, {} mod {}, {} mod {})",
+                u64::MAX - 125,
+                u64::MAX,
+                u64::MAX - 1 - 64,
+                u64::MAX - 1,
+                u64::MAX - 2 - 27,
+                u64::MAX - 2
+            )
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use std::error::Error;
+use std::io::{BufRead, Write};
+
+use itertools::Itertools;
+
+use crate::shared::cli::{Cli, Options};
+use crate::shared::grid::Position;
+use crate::shared::image;
+use crate::shared::runner::{parse_input, Part};
+use crate::shared::search::{bfs, Path};
+use crate::shared::{Grid, ParseError, Solver};
+
+pub struct Day12;
+
+pub struct Heightmap {
+    /// Heights as character codes, `S` and `E` are replaced by `a` and `z`
+    pub map: Grid<u32>,
+    pub start: Position,
+    pub end: Position,
+}
+
+fn parse_heightmap(input: &str) -> Result<Heightmap, ParseError> {
+    let mut map = Grid::parse(input.trim_end(), "a height from 'a' to 'z'", |c| match c {
+        'a'..='z' | 'S' | 'E' => Some(c as u32),
+        _ => None,
+    })?;
+
+    let find = |val: char| map.iter().find(|&(_, &v)| v == val as u32).map(|(p, _)| p);
+    let start =
+        find('S').ok_or_else(|| ParseError::end_of_input(input, "expected the start 'S'"))?;
+    let end = find('E').ok_or_else(|| ParseError::end_of_input(input, "expected the end 'E'"))?;
+
+    map[start] = 'a' as u32;
+    map[end] = 'z' as u32;
+
+    Ok(Heightmap { map, start, end })
+}
+
+/// Shortest climb from any of `starts` to `end`, every step may go at most one higher.
+pub fn climb(
+    map: &Grid<u32>,
+    starts: impl IntoIterator<Item = Position>,
+    end: Position,
+) -> Option<Path<Position>> {
+    bfs(
+        starts,
+        |&p| map.neighbours4(p).filter(move |&n| map[n] <= map[p] + 1),
+        |&p| p == end,
+    )
+}
+
+impl Heightmap {
+    /// The route of part a, from `S` to `E`.
+    pub fn route_from_start(&self) -> Option<Path<Position>> {
+        climb(&self.map, [self.start], self.end)
+    }
+
+    /// The route of part b, from the lowest square closest to `E`.
+    pub fn route_from_lowest(&self) -> Option<Path<Position>> {
+        let lowest = self
+            .map
+            .iter()
+            .filter(|&(_, &height)| height == 'a' as u32)
+            .map(|(p, _)| p);
+
+        climb(&self.map, lowest, self.end)
+    }
+}
+
+/// Draws the route the way the puzzle description does, every square on it shows an arrow
+/// towards the next one and the end is marked `E`.
+pub fn render_route(map: &Grid<u32>, route: &[Position]) -> String {
+    let mut picture = Grid::new(map.width(), map.height(), '.');
+
+    for (&p, &(nx, ny)) in route.iter().tuple_windows() {
+        picture[p] = match (nx as isize - p.0 as isize, ny as isize - p.1 as isize) {
+            (1, 0) => '>',
+            (-1, 0) => '<',
+            (0, 1) => 'v',
+            (0, -1) => '^',
+            _ => '?',
+        };
+    }
+    if let Some(&end) = route.last() {
+        picture[end] = 'E';
+    }
+
+    picture.to_string()
+}
+
+/// The heightmap as a binary PPM image with `scale` pixels per square, higher squares are
+/// lighter, the route is red and its end yellow.
+pub fn route_image(map: &Grid<u32>, route: &[Position], scale: usize) -> Vec<u8> {
+    let mut colors = map.map(|&height| {
+        let shade = 40 + 8 * height.saturating_sub('a' as u32).min(25) as u8;
+        [shade, shade, shade]
+    });
+    for &p in route {
+        colors[p] = [220, 30, 30];
+    }
+    if let Some(&end) = route.last() {
+        colors[end] = [255, 220, 0];
+    }
+
+    image::ppm(&colors, scale)
+}
+
+/// With `--route` the route is drawn below the answer, `--ppm <file>` saves it as an image
+/// scaled by `--scale` (4 by default).
+pub const CLI: Cli = Cli {
+    flags: &["--route"],
+    valued: &["--ppm", "--scale"],
+    run,
+};
+
+fn run(
+    part: Part,
+    options: &Options,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let heightmap = parse_input::<Day12>(input)?;
+    let route = match part {
+        Part::A => heightmap.route_from_start(),
+        Part::B => heightmap.route_from_lowest(),
+    };
+    let route = route.ok_or("The input has no answer")?;
+    writeln!(output, "{}", route.cost)?;
+
+    if options.flag("--route") {
+        writeln!(output, "{}", render_route(&heightmap.map, &route.nodes))?;
+    }
+
+    if let Some(file) = options.value("--ppm") {
+        let scale = options.parsed::<usize>("--scale")?.unwrap_or(4);
+        let image = route_image(&heightmap.map, &route.nodes, scale);
+        std::fs::write(file, image).map_err(|e| format!("Cannot write {file}: {e}"))?;
+    }
+
+    Ok(())
+}
+
+impl Solver for Day12 {
+    const DAY: u32 = 12;
+
+    type Input = Heightmap;
+    type AnswerA = u32;
+    type AnswerB = u32;
+
+    fn parse(input: &str) -> Result<Heightmap, ParseError> {
+        parse_heightmap(input)
+    }
+
+    fn part_a(heightmap: &Heightmap) -> Option<u32> {
+        Some(heightmap.route_from_start()?.cost)
+    }
+
+    fn part_b(heightmap: &Heightmap) -> Option<u32> {
+        Some(heightmap.route_from_lowest()?.cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi";
+
+    #[test]
+    fn example() {
+        let heightmap = Day12::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day12::part_a(&heightmap), Some(31));
+        assert_eq!(Day12::part_b(&heightmap), Some(29));
+    }
+
+    #[test]
+    fn route_is_drawn_below_the_answer() {
+        assert_eq!(
+            CLI.run_on(Part::A, &["--route"], EXAMPLE).unwrap(),
+            "31\n>>vv<<<<\n..vvv<<^\n..vv>E^^\n..v>>>^^\n..>>>>>^\n"
+        );
+
+        let output = CLI.run_on(Part::B, &["--route"], EXAMPLE).unwrap();
+        assert!(output.starts_with("29\n"));
+        assert_eq!(output.matches(['>', '<', '^', 'v']).count(), 29);
+    }
+}
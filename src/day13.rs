@@ -0,0 +1,538 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::shared::parse::LineError;
+use crate::shared::{ParseError, Solver};
+
+pub struct Day13;
+
+/// A packet or a part of one, written like `[1,[2,3],4]`. Equality compares the elements as
+/// written, packets are put in order with `compare`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Element {
+    Number(i32),
+    List(Vec<Element>),
+}
+
+/// Recursive descent over the text of an element, `at` is the byte offset of the next character.
+struct ElementParser<'a> {
+    s: &'a str,
+    at: usize,
+}
+
+impl ElementParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.s.as_bytes().get(self.at).copied()
+    }
+
+    fn error(&self, message: impl Into<String>) -> LineError {
+        LineError::new(self.s[..self.at].chars().count() + 1, message)
+    }
+
+    fn unexpected(&self, expected: &str) -> LineError {
+        match self.s[self.at..].chars().next() {
+            Some(c) => self.error(format!("expected {expected}, found '{c}'")),
+            None => self.error(format!("expected {expected}")),
+        }
+    }
+
+    fn element(&mut self) -> Result<Element, LineError> {
+        match self.peek() {
+            Some(b'[') => self.list(),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => Err(self.unexpected("a number or '['")),
+        }
+    }
+
+    fn list(&mut self) -> Result<Element, LineError> {
+        self.at += 1;
+        let mut elements = Vec::<Element>::new();
+        if self.peek() == Some(b']') {
+            self.at += 1;
+            return Ok(Element::List(elements));
+        }
+
+        loop {
+            elements.push(self.element()?);
+            match self.peek() {
+                Some(b',') => self.at += 1,
+                Some(b']') => {
+                    self.at += 1;
+                    return Ok(Element::List(elements));
+                }
+                _ => return Err(self.unexpected("',' or ']'")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Element, LineError> {
+        let start = self.at;
+        if self.peek() == Some(b'-') {
+            self.at += 1;
+        }
+        let digits = self.at;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.at += 1;
+        }
+        if self.at == digits {
+            return Err(self.unexpected("a digit"));
+        }
+
+        // Only the way `Display` writes numbers is accepted, so that printing gives the text back
+        let column = |at: usize| self.s[..at].chars().count() + 1;
+        match &self.s[digits..self.at] {
+            "0" if start < digits => {
+                return Err(LineError::new(column(start), "expected 0 without a sign"))
+            }
+            written if written.len() > 1 && written.starts_with('0') => {
+                return Err(LineError::new(
+                    column(digits),
+                    "expected a number without leading zeros",
+                ))
+            }
+            _ => (),
+        }
+
+        self.s[start..self.at]
+            .parse::<i32>()
+            .map(Element::Number)
+            .map_err(|_| LineError::new(column(start), "number is out of range"))
+    }
+}
+
+/// Errors are located by their column in `s`. Numbers are written without leading zeros and `0`
+/// without a sign, so every element that parses prints back as the same text.
+impl FromStr for Element {
+    type Err = LineError;
+
+    fn from_str(s: &str) -> Result<Element, LineError> {
+        let mut parser = ElementParser { s, at: 0 };
+        let element = parser.element()?;
+        if parser.at < s.len() {
+            return Err(parser.unexpected("the end of the packet"));
+        }
+
+        Ok(element)
+    }
+}
+
+/// Writes the element back the way the input does, without any spaces.
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Element::Number(n) => write!(f, "{n}"),
+            Element::List(elements) => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl From<&Element> for Value {
+    fn from(element: &Element) -> Value {
+        match element {
+            Element::Number(n) => Value::from(*n),
+            Element::List(elements) => Value::Array(elements.iter().map(Value::from).collect()),
+        }
+    }
+}
+
+/// Accepts arrays nested to any depth with integers that fit into an `i32`.
+impl TryFrom<&Value> for Element {
+    type Error = String;
+
+    fn try_from(value: &Value) -> Result<Element, String> {
+        match value {
+            Value::Array(values) => values
+                .iter()
+                .map(Element::try_from)
+                .collect::<Result<Vec<Element>, String>>()
+                .map(Element::List),
+            Value::Number(n) => n
+                .as_i64()
+                .and_then(|n| i32::try_from(n).ok())
+                .map(Element::Number)
+                .ok_or_else(|| format!("expected a 32-bit integer, found {n}")),
+            _ => Err(format!("expected an array or an integer, found {value}")),
+        }
+    }
+}
+
+/// A packet is always a list.
+pub fn parse_packet(s: &str) -> Result<Element, LineError> {
+    if !s.starts_with('[') {
+        return Err(LineError::new(1, "expected '['"));
+    }
+
+    s.parse()
+}
+
+fn parse_pairs(input: &str) -> Result<Vec<(Element, Element)>, ParseError> {
+    let lines = input.lines().enumerate().collect::<Vec<(usize, &str)>>();
+
+    lines
+        .split(|(_, line)| line.is_empty())
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let packet =
+                |&(i, line): &(usize, &str)| parse_packet(line).map_err(|e| e.on_line(i + 1, line));
+
+            match pair {
+                [left_p, right_p] => Ok((packet(left_p)?, packet(right_p)?)),
+                [(i, _)] => Err(ParseError::new(
+                    i + 2,
+                    1,
+                    "",
+                    "expected the second packet of the pair",
+                )),
+                [_, _, (i, line), ..] => Err(ParseError::new(
+                    i + 1,
+                    1,
+                    line,
+                    "expected an empty line after a pair",
+                )),
+                [] => unreachable!("Empty groups are filtered out"),
+            }
+        })
+        .collect()
+}
+
+/// The packet order, in which a number compares like the list holding only it. Packets that are
+/// not `==` can compare equal, like `[1]` and `[[1]]`, so this is not `Ord`.
+pub fn compare(left_element: &Element, right_element: &Element) -> Ordering {
+    match left_element {
+        Element::List(left_sublist) => match right_element {
+            Element::List(right_sublist) => {
+                let maybe_result = left_sublist
+                    .iter()
+                    .zip(right_sublist.iter())
+                    .map(|(le, re)| compare(le, re))
+                    .find(|&ord| ord != Ordering::Equal);
+
+                if let Some(result) = maybe_result {
+                    result
+                } else {
+                    left_sublist.len().cmp(&right_sublist.len())
+                }
+            }
+            Element::Number(right_num) => compare(
+                left_element,
+                &Element::List(vec![Element::Number(*right_num)]),
+            ),
+        },
+        Element::Number(left_num) => match right_element {
+            Element::List(_) => compare(
+                &Element::List(vec![Element::Number(*left_num)]),
+                right_element,
+            ),
+            Element::Number(right_num) => left_num.cmp(right_num),
+        },
+    }
+}
+
+/// The divider packets of the puzzle.
+pub fn dividers() -> [Element; 2] {
+    [
+        Element::List(vec![Element::List(vec![Element::Number(2)])]),
+        Element::List(vec![Element::List(vec![Element::Number(6)])]),
+    ]
+}
+
+/// All packets of the pairs in the order of the input.
+pub fn packets(pairs: &[(Element, Element)]) -> Vec<&Element> {
+    pairs
+        .iter()
+        .flat_map(|(left, right)| [left, right])
+        .collect()
+}
+
+/// The packets in the right order, equal packets keep their order from the input.
+pub fn sorted<'a>(packets: impl IntoIterator<Item = &'a Element>) -> Vec<&'a Element> {
+    let mut sorted = packets.into_iter().collect::<Vec<&Element>>();
+    sorted.sort_by(|a, b| compare(a, b));
+
+    sorted
+}
+
+/// Position counted from 1 that each divider takes in `sorted` of the packets followed by the
+/// dividers, so packets equal to a divider come before it. Only the packets ordered before each
+/// divider are counted, so no full sort is needed.
+pub fn ranks(packets: &[&Element], dividers: &[Element]) -> Vec<usize> {
+    dividers
+        .iter()
+        .enumerate()
+        .map(|(i, divider)| {
+            let before_packets = packets
+                .iter()
+                .filter(|p| compare(p, divider).is_le())
+                .count();
+            let before_dividers = dividers
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| match compare(other, divider) {
+                    Ordering::Less => true,
+                    Ordering::Equal => j < i,
+                    Ordering::Greater => false,
+                })
+                .count();
+
+            before_packets + before_dividers + 1
+        })
+        .collect()
+}
+
+/// Groups of indices of packets that are equal in packet order, such as `[1]` and `[[1]]`, each
+/// group sorted and the groups in the order of their first packet.
+pub fn duplicates(packets: &[&Element]) -> Vec<Vec<usize>> {
+    let mut indices = (0..packets.len()).collect::<Vec<usize>>();
+    indices.sort_by(|&a, &b| compare(packets[a], packets[b]));
+
+    let mut groups = indices
+        .chunk_by(|&a, &b| compare(packets[a], packets[b]).is_eq())
+        .filter(|group| group.len() > 1)
+        .map(|group| {
+            let mut group = group.to_vec();
+            group.sort_unstable();
+            group
+        })
+        .collect::<Vec<Vec<usize>>>();
+    groups.sort();
+
+    groups
+}
+
+impl Solver for Day13 {
+    const DAY: u32 = 13;
+
+    type Input = Vec<(Element, Element)>;
+    type AnswerA = usize;
+    type AnswerB = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_pairs(input)
+    }
+
+    fn part_a(pairs: &Vec<(Element, Element)>) -> Option<usize> {
+        let sum = pairs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (left_list, right_list))| {
+                if compare(left_list, right_list) != Ordering::Greater {
+                    Some(index + 1)
+                } else {
+                    None
+                }
+            })
+            .sum::<usize>();
+
+        Some(sum)
+    }
+
+    fn part_b(pairs: &Vec<(Element, Element)>) -> Option<usize> {
+        let ranks = ranks(&packets(pairs), &dividers());
+
+        Some(ranks.iter().product())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::random::Random;
+
+    fn random_element(random: &mut Random, depth: usize) -> Element {
+        if depth == 0 || random.below(3) == 0 {
+            let n = match random.below(4) {
+                0 => [0, 1, -1, i32::MIN, i32::MAX][random.below(5)],
+                1 => random.next() as i32,
+                _ => random.below(20) as i32 - 5,
+            };
+            return Element::Number(n);
+        }
+
+        let len = random.below(5);
+        Element::List(
+            (0..len)
+                .map(|_| random_element(random, depth - 1))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn printed_elements_parse_back() {
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..2000 {
+            let element = random_element(&mut random, 5);
+            let text = element.to_string();
+
+            assert_eq!(text.parse::<Element>(), Ok(element), "{text}");
+        }
+    }
+
+    #[test]
+    fn elements_convert_to_json_and_back() {
+        let mut random = Random(0x2545_f491_4f6c_dd1d);
+        for _ in 0..2000 {
+            let element = random_element(&mut random, 5);
+            let value = Value::from(&element);
+
+            assert_eq!(Element::try_from(&value), Ok(element.clone()));
+            // Packets are written like JSON arrays
+            assert_eq!(
+                serde_json::from_str::<Value>(&element.to_string()).unwrap(),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn json_that_is_not_a_packet_is_rejected() {
+        for text in ["[1.5]", "[\"a\"]", "[null]", "[4294967296]", "{}"] {
+            let value = serde_json::from_str::<Value>(text).unwrap();
+            assert!(Element::try_from(&value).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn text_that_parses_prints_back_unchanged() {
+        let alphabet = b"[],-0123456789";
+        let mut random = Random(0xd1b5_4a32_d192_ed03);
+        let mut parsed = 0;
+        for _ in 0..50_000 {
+            let len = 1 + random.below(12);
+            let text = (0..len)
+                .map(|_| alphabet[random.below(alphabet.len())] as char)
+                .collect::<String>();
+
+            if let Ok(element) = text.parse::<Element>() {
+                assert_eq!(element.to_string(), text);
+                parsed += 1;
+            }
+        }
+        assert!(parsed > 100);
+    }
+
+    #[test]
+    fn numbers_must_be_canonical() {
+        for (text, column) in [("[-0]", 2), ("[007]", 2), ("[1,00]", 4), ("[-01]", 3)] {
+            let e = text.parse::<Element>().unwrap_err();
+            assert_eq!(e.column, column, "{text}");
+        }
+        for text in ["[0]", "[-7]", "[10]", "[-2147483648]"] {
+            assert_eq!(text.parse::<Element>().unwrap().to_string(), text);
+        }
+    }
+
+    fn packet(text: &str) -> Element {
+        parse_packet(text).unwrap()
+    }
+
+    #[test]
+    fn lists_and_numbers_compare_equal_without_being_equal() {
+        assert_eq!(compare(&packet("[1]"), &packet("[[1]]")), Ordering::Equal);
+        assert_ne!(packet("[1]"), packet("[[1]]"));
+        assert_eq!(
+            compare(&packet("[[1],4]"), &packet("[1,3]")),
+            Ordering::Greater
+        );
+        assert_eq!(compare(&packet("[]"), &packet("[[]]")), Ordering::Less);
+    }
+
+    #[test]
+    fn ranks_are_positions_in_the_sorted_packets() {
+        let packets = ["[1]", "[[2]]", "[2]", "[3]", "[[6],1]", "[6]"].map(packet);
+        let packets = packets.iter().collect::<Vec<&Element>>();
+        let dividers = dividers();
+
+        // [[2]] and [2] tie with the first divider and [6] with the second
+        assert_eq!(ranks(&packets, &dividers), [4, 7]);
+        let sorted = sorted(packets.iter().copied().chain(&dividers));
+        assert!(std::ptr::eq(sorted[3], &dividers[0]));
+        assert!(std::ptr::eq(sorted[6], &dividers[1]));
+
+        let twice = [dividers[1].clone(), dividers[1].clone()];
+        assert_eq!(ranks(&packets, &twice), [6, 7]);
+        assert_eq!(ranks(&[], &dividers), [1, 2]);
+    }
+
+    /// Numbers up to 7 and short lists, which often tie with the dividers.
+    fn small_element(random: &mut Random, depth: usize) -> Element {
+        if depth == 0 || random.below(2) == 0 {
+            return Element::Number(random.below(8) as i32);
+        }
+
+        let len = random.below(3);
+        Element::List((0..len).map(|_| small_element(random, depth - 1)).collect())
+    }
+
+    #[test]
+    fn ranks_match_a_sort_of_random_packets() {
+        let mut random = Random(13);
+        for _ in 0..500 {
+            let packets = (0..random.below(8))
+                .map(|_| Element::List(vec![small_element(&mut random, 3)]))
+                .collect::<Vec<Element>>();
+            let packets = packets.iter().collect::<Vec<&Element>>();
+            let dividers = dividers();
+
+            let sorted = sorted(packets.iter().copied().chain(&dividers));
+            let positions = dividers
+                .iter()
+                .map(|d| sorted.iter().position(|&p| std::ptr::eq(p, d)).unwrap() + 1)
+                .collect::<Vec<usize>>();
+            assert_eq!(ranks(&packets, &dividers), positions, "{packets:?}");
+        }
+    }
+
+    #[test]
+    fn duplicates_are_grouped_in_packet_order() {
+        let packets = ["[[1]]", "[2]", "[1]", "[[2]]", "[[[1]]]", "[3]", "[1,1]"].map(packet);
+        let packets = packets.iter().collect::<Vec<&Element>>();
+
+        assert_eq!(duplicates(&packets), [vec![0, 2, 4], vec![1, 3]]);
+        assert_eq!(duplicates(&packets[4..]), Vec::<Vec<usize>>::new());
+    }
+
+    #[test]
+    fn example() {
+        let input = "\
+[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+        let pairs = Day13::parse(input).unwrap();
+
+        assert_eq!(Day13::part_a(&pairs), Some(13));
+        assert_eq!(Day13::part_b(&pairs), Some(140));
+    }
+}
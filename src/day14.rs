@@ -0,0 +1,523 @@
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{BufRead, Write};
+
+use crate::shared::cli::{Cli, Options};
+use crate::shared::image;
+use crate::shared::parse::{number, parse_lines, split_once, LineError};
+use crate::shared::runner::{parse_input, Part};
+use crate::shared::{Grid, ParseError, Solver};
+
+pub struct Day14;
+
+/// A point of the cave as `(x, y)`, `y` grows downwards.
+pub type Point = (i64, i64);
+pub type RockPath = Vec<Point>;
+
+pub const SOURCE: Point = (500, 0);
+
+fn points_between_nodes(&(x1, y1): &Point, &(x2, y2): &Point) -> Vec<Point> {
+    if x1 == x2 {
+        (y1.min(y2)..=y1.max(y2)).map(|y| (x1, y)).collect()
+    } else if y1 == y2 {
+        (x1.min(x2)..=x1.max(x2)).map(|x| (x, y1)).collect()
+    } else {
+        panic!("No coordinates are common")
+    }
+}
+
+fn parse_rock_path(line: &str) -> Result<RockPath, LineError> {
+    let nodes_parts = line.split(" -> ").collect::<Vec<&str>>();
+    let path = nodes_parts
+        .iter()
+        .map(|coords_part| {
+            let (x_part, y_part) = split_once(line, coords_part, ",")?;
+            Ok((number(line, x_part)?, number(line, y_part)?))
+        })
+        .collect::<Result<RockPath, LineError>>()?;
+
+    for (k, ((x1, y1), (x2, y2))) in path.iter().tuple_windows().enumerate() {
+        if x1 != x2 && y1 != y2 {
+            return Err(LineError::at(
+                line,
+                nodes_parts[k + 1],
+                "expected a horizontal or vertical line of rock",
+            ));
+        }
+    }
+
+    Ok(path)
+}
+
+pub fn rock_points(paths: &[RockPath]) -> impl Iterator<Item = Point> + '_ {
+    paths.iter().flat_map(|path| {
+        path.iter()
+            .tuple_windows()
+            .flat_map(|(last, node)| points_between_nodes(last, node))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Rock,
+    Sand,
+}
+
+/// Rock and settled sand, only the occupied points are stored so the cave can lie anywhere.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    cells: HashMap<Point, Cell>,
+    source: Point,
+    /// The lowest rock, sand never changes it
+    max_y: i64,
+}
+
+impl Cave {
+    pub fn new(paths: &[RockPath], source: Point) -> Cave {
+        let cells = rock_points(paths)
+            .map(|p| (p, Cell::Rock))
+            .collect::<HashMap<Point, Cell>>();
+        let max_y = cells.keys().map(|p| p.1).max().unwrap_or(source.1);
+
+        Cave {
+            cells,
+            source,
+            max_y,
+        }
+    }
+
+    pub fn source(&self) -> Point {
+        self.source
+    }
+
+    pub fn max_y(&self) -> i64 {
+        self.max_y
+    }
+
+    pub fn get(&self, p: Point) -> Option<Cell> {
+        self.cells.get(&p).copied()
+    }
+
+    /// Every occupied point with what occupies it, in no particular order.
+    pub fn cells(&self) -> impl Iterator<Item = (Point, Cell)> + '_ {
+        self.cells.iter().map(|(&p, &cell)| (p, cell))
+    }
+
+    pub fn sand_count(&self) -> usize {
+        self.cells.values().filter(|&&c| c == Cell::Sand).count()
+    }
+
+    /// The part of the cave holding rock, sand and the source with one more point on the sides
+    /// and below, and the point its top left corner stands for. Drawn like the puzzle does, with
+    /// `#` for rock and points blocked by the boundary, `o` for sand and `+` for the source.
+    /// Rock far apart would need a huge picture, so pictures over `PICTURE_LIMIT` points are
+    /// refused.
+    pub fn picture(
+        &self,
+        boundary: &impl Boundary,
+    ) -> Result<(Point, Grid<char>), PictureTooLarge> {
+        let points = || self.cells.keys().chain([&self.source]);
+        let min_x = points().map(|p| p.0).min().expect("There is a source") as i128 - 1;
+        let max_x = points().map(|p| p.0).max().expect("There is a source") as i128 + 1;
+        let min_y = points().map(|p| p.1).min().expect("There is a source") as i128;
+        let max_y = points().map(|p| p.1).max().expect("There is a source") as i128 + 1;
+
+        let width = (max_x - min_x + 1) as u128;
+        let height = (max_y - min_y + 1) as u128;
+        if width * height > PICTURE_LIMIT as u128 {
+            return Err(PictureTooLarge { width, height });
+        }
+
+        let (min_x, min_y) = (min_x as i64, min_y as i64);
+        let mut picture = Grid::new(width as usize, height as usize, '.');
+        for (x, y) in picture.positions().collect::<Vec<(usize, usize)>>() {
+            let p = (min_x + x as i64, min_y + y as i64);
+            picture[(x, y)] = match self.get(p) {
+                Some(Cell::Rock) => '#',
+                Some(Cell::Sand) => 'o',
+                None if p == self.source => '+',
+                None if boundary.blocks(p) => '#',
+                None => '.',
+            };
+        }
+
+        Ok(((min_x, min_y), picture))
+    }
+
+    pub fn render(&self, boundary: &impl Boundary) -> Result<String, PictureTooLarge> {
+        Ok(self.picture(boundary)?.1.to_string())
+    }
+
+    /// Drops grains of sand from the source one after another, see `Sandfall`.
+    pub fn sandfall<B: Boundary>(&mut self, boundary: B) -> Sandfall<'_, B> {
+        Sandfall {
+            cave: self,
+            boundary,
+            path: Vec::new(),
+        }
+    }
+}
+
+/// The most points `Cave::picture` draws.
+pub const PICTURE_LIMIT: usize = 1 << 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PictureTooLarge {
+    pub width: u128,
+    pub height: u128,
+}
+
+impl fmt::Display for PictureTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the cave is {} by {} points, pictures have at most {PICTURE_LIMIT}",
+            self.width, self.height
+        )
+    }
+}
+
+impl Error for PictureTooLarge {}
+
+/// What surrounds the rock of a cave.
+pub trait Boundary {
+    /// Whether sand cannot enter `p` because of the boundary.
+    fn blocks(&self, p: Point) -> bool;
+
+    /// Whether sand at `p` falls forever.
+    fn is_lost(&self, p: Point) -> bool;
+}
+
+/// Sand falling below the lowest rock is lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Abyss {
+    pub bottom: i64,
+}
+
+impl Abyss {
+    pub fn below(cave: &Cave) -> Abyss {
+        Abyss {
+            bottom: cave.max_y(),
+        }
+    }
+}
+
+impl Boundary for Abyss {
+    fn blocks(&self, _p: Point) -> bool {
+        false
+    }
+
+    fn is_lost(&self, (_, y): Point) -> bool {
+        y > self.bottom
+    }
+}
+
+/// An infinite floor at height `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Floor {
+    pub y: i64,
+}
+
+impl Floor {
+    /// The floor of the puzzle, two below the lowest rock.
+    pub fn below(cave: &Cave) -> Floor {
+        Floor {
+            y: cave.max_y() + 2,
+        }
+    }
+}
+
+impl Boundary for Floor {
+    fn blocks(&self, (_, y): Point) -> bool {
+        y >= self.y
+    }
+
+    fn is_lost(&self, _p: Point) -> bool {
+        false
+    }
+}
+
+/// A box open at the top, sand stays between `left` and `right` and above `floor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Walls {
+    pub left: i64,
+    pub right: i64,
+    pub floor: i64,
+}
+
+impl Boundary for Walls {
+    fn blocks(&self, (x, y): Point) -> bool {
+        x < self.left || x > self.right || y >= self.floor
+    }
+
+    fn is_lost(&self, _p: Point) -> bool {
+        false
+    }
+}
+
+/// Yields where every grain of sand comes to rest until a grain is lost or the source is
+/// covered. Falling is iterative and the path of the last grain is kept, so every grain starts
+/// from where the previous one left that path rather than from the source.
+pub struct Sandfall<'a, B> {
+    cave: &'a mut Cave,
+    boundary: B,
+    path: Vec<Point>,
+}
+
+impl<B: Boundary> Sandfall<'_, B> {
+    pub fn cave(&self) -> &Cave {
+        self.cave
+    }
+
+    pub fn boundary(&self) -> &B {
+        &self.boundary
+    }
+
+    fn is_free(&self, p: Point) -> bool {
+        !self.cave.cells.contains_key(&p) && !self.boundary.blocks(p)
+    }
+}
+
+impl<B: Boundary> Iterator for Sandfall<'_, B> {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if self.path.is_empty() {
+            if !self.is_free(self.cave.source) {
+                return None;
+            }
+            self.path.push(self.cave.source);
+        }
+
+        loop {
+            let &(x, y) = self.path.last()?;
+            if self.boundary.is_lost((x, y)) {
+                return None;
+            }
+
+            match [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)]
+                .into_iter()
+                .find(|&p| self.is_free(p))
+            {
+                Some(next) => self.path.push(next),
+                None => {
+                    self.path.pop();
+                    self.cave.cells.insert((x, y), Cell::Sand);
+                    return Some((x, y));
+                }
+            }
+        }
+    }
+}
+
+/// A picture of the cave as a binary PPM image, every point becomes a square of `scale` pixels.
+pub fn cave_image(picture: &Grid<char>, scale: usize) -> Vec<u8> {
+    let colors = picture.map(|&c| match c {
+        '#' => [110, 110, 120],
+        'o' => [230, 190, 90],
+        '+' => [220, 30, 30],
+        _ => [20, 20, 30],
+    });
+
+    image::ppm(&colors, scale)
+}
+
+/// `--frames <n>` draws the cave after every `n` grains of sand and `--cave` once all sand has
+/// settled. `--ppm <file>` saves the final cave as an image scaled by `--scale` (4 by default).
+/// Part A lets sand fall into the abyss, part B stops it on the floor.
+pub const CLI: Cli = Cli {
+    flags: &["--cave"],
+    valued: &["--frames", "--ppm", "--scale"],
+    run,
+};
+
+fn run(
+    part: Part,
+    options: &Options,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let paths = parse_input::<Day14>(input)?;
+    let cave = Cave::new(&paths, SOURCE);
+    match part {
+        Part::A => {
+            let abyss = Abyss::below(&cave);
+            draw(cave, abyss, options, output)
+        }
+        Part::B => {
+            let floor = Floor::below(&cave);
+            draw(cave, floor, options, output)
+        }
+    }
+}
+
+fn draw<B: Boundary + Clone>(
+    mut cave: Cave,
+    boundary: B,
+    options: &Options,
+    output: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let every = options.parsed::<usize>("--frames")?;
+    if every == Some(0) {
+        return Err("The number of grains between frames must be positive".into());
+    }
+
+    let mut sandfall = cave.sandfall(boundary.clone());
+    let mut count = 0;
+    while sandfall.next().is_some() {
+        count += 1;
+        if every.is_some_and(|n| count % n == 0) {
+            let picture = sandfall.cave().render(sandfall.boundary())?;
+            writeln!(output, "After {count} grains:\n{picture}\n")?;
+        }
+    }
+    writeln!(output, "{count}")?;
+
+    if options.flag("--cave") {
+        writeln!(output, "{}", cave.render(&boundary)?)?;
+    }
+
+    if let Some(file) = options.value("--ppm") {
+        let scale = options.parsed::<usize>("--scale")?.unwrap_or(4);
+        let image = cave_image(&cave.picture(&boundary)?.1, scale);
+        std::fs::write(file, image).map_err(|e| format!("Cannot write {file}: {e}"))?;
+    }
+
+    Ok(())
+}
+
+impl Solver for Day14 {
+    const DAY: u32 = 14;
+
+    type Input = Vec<RockPath>;
+    type AnswerA = usize;
+    type AnswerB = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, parse_rock_path)
+    }
+
+    fn part_a(rock_paths: &Vec<RockPath>) -> Option<usize> {
+        let mut cave = Cave::new(rock_paths, SOURCE);
+        let abyss = Abyss::below(&cave);
+
+        Some(cave.sandfall(abyss).count())
+    }
+
+    fn part_b(rock_paths: &Vec<RockPath>) -> Option<usize> {
+        let mut cave = Cave::new(rock_paths, SOURCE);
+        let floor = Floor::below(&cave);
+
+        Some(cave.sandfall(floor).count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9
+";
+
+    fn example_cave() -> Cave {
+        Cave::new(&Day14::parse(EXAMPLE).unwrap(), SOURCE)
+    }
+
+    #[test]
+    fn sand_falls_into_the_abyss() {
+        let mut cave = example_cave();
+        let abyss = Abyss::below(&cave);
+        assert_eq!(abyss, Abyss { bottom: 9 });
+        assert_eq!(cave.sandfall(abyss).count(), 24);
+        assert_eq!(
+            cave.render(&abyss).unwrap(),
+            [
+                ".......+....",
+                "............",
+                ".......o....",
+                "......ooo...",
+                ".....#ooo##.",
+                "....o#ooo#..",
+                "...###ooo#..",
+                ".....oooo#..",
+                "..o.ooooo#..",
+                ".#########..",
+                "............",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn sand_piles_up_on_the_floor() {
+        let mut cave = example_cave();
+        let floor = Floor::below(&cave);
+        assert_eq!(floor, Floor { y: 11 });
+        assert_eq!(cave.sandfall(floor).count(), 93);
+        assert_eq!(cave.get(SOURCE), Some(Cell::Sand));
+        assert_eq!(cave.get((490, 10)), Some(Cell::Sand));
+        assert_eq!(cave.get((489, 10)), None);
+        assert!(cave.cells().all(|((_, y), _)| y < 11));
+    }
+
+    #[test]
+    fn sand_fills_a_box_of_walls() {
+        let mut cave = Cave::new(&[], SOURCE);
+        let walls = Walls {
+            left: 499,
+            right: 501,
+            floor: 3,
+        };
+        let grains = cave.sandfall(walls).collect::<Vec<Point>>();
+        assert_eq!(
+            grains,
+            [
+                (500, 2),
+                (499, 2),
+                (501, 2),
+                (500, 1),
+                (499, 1),
+                (501, 1),
+                (500, 0),
+            ]
+        );
+        assert_eq!(cave.render(&walls).unwrap(), "#.o.#\n#ooo#\n#ooo#\n#####");
+    }
+
+    #[test]
+    fn sand_stops_when_the_source_is_covered() {
+        let mut cave = Cave::new(&[vec![(500, 0), (500, 0)]], SOURCE);
+        assert_eq!(cave.sandfall(Floor { y: 10 }).count(), 0);
+    }
+
+    #[test]
+    fn far_apart_rock_is_not_pictured() {
+        let paths = vec![
+            vec![(0, 0), (0, 1)],
+            vec![(i64::MAX - 1, 5), (i64::MAX - 1, 6)],
+        ];
+        let cave = Cave::new(&paths, SOURCE);
+        assert_eq!(
+            cave.picture(&Abyss::below(&cave)).err(),
+            Some(PictureTooLarge {
+                width: i64::MAX as u128 + 2,
+                height: 8,
+            })
+        );
+        assert!(CLI
+            .run_on(Part::A, &["--cave"], "0,0 -> 0,1\n5000,0 -> 5000,5000\n")
+            .is_err());
+    }
+
+    #[test]
+    fn frames_are_drawn_between_grains() {
+        let output = CLI.run_on(Part::A, &["--frames", "10"], EXAMPLE).unwrap();
+        assert!(output.starts_with("After 10 grains:\n"));
+        assert!(output.contains("\nAfter 20 grains:\n"));
+        assert!(output.ends_with("\n\n24\n"));
+        assert_eq!(CLI.run_on(Part::B, &[], EXAMPLE).unwrap(), "93\n");
+    }
+}
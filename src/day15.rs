@@ -0,0 +1,429 @@
+use itertools::Itertools;
+
+use crate::shared::parse::{number, parse_lines, split_once, strip_prefix, LineError};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day15;
+
+/// A point as `(x, y)`, `y` grows downwards.
+pub type Point = (i64, i64);
+
+/// The row of part A.
+pub const ROW: i64 = 2_000_000;
+/// The lost beacon is within `0..=SEARCH_SIZE` in both coordinates.
+pub const SEARCH_SIZE: i64 = 4_000_000;
+
+#[derive(Debug)]
+pub struct Reading {
+    pub sensor: Point,
+    pub beacon: Point,
+}
+
+impl Reading {
+    /// How far the sensor sees, there is no other beacon this close to it.
+    pub fn radius(&self) -> i64 {
+        manhattan(&self.sensor, &self.beacon)
+    }
+}
+
+fn parse_coordinates(line: &str, s: &str) -> Result<Point, LineError> {
+    let (left_part, right_part) = split_once(line, s, ", ")?;
+
+    Ok((
+        number(line, strip_prefix(line, left_part, "x=")?)?,
+        number(line, strip_prefix(line, right_part, "y=")?)?,
+    ))
+}
+
+fn parse_reading(line: &str) -> Result<Reading, LineError> {
+    let rest = strip_prefix(line, line, "Sensor at ")?;
+    let (sensor_part, beacon_part) = split_once(line, rest, ": ")?;
+    let beacon_part = strip_prefix(line, beacon_part, "closest beacon is at ")?;
+
+    Ok(Reading {
+        sensor: parse_coordinates(line, sensor_part)?,
+        beacon: parse_coordinates(line, beacon_part)?,
+    })
+}
+
+pub fn manhattan((x1, y1): &Point, (x2, y2): &Point) -> i64 {
+    i64::abs(x2 - x1) + i64::abs(y2 - y1)
+}
+
+/// Inclusive range of coordinates `(first, last)`.
+pub type Interval = (i64, i64);
+
+fn interval_len((first, last): &Interval) -> u64 {
+    last.abs_diff(*first) + 1
+}
+
+/// Sorted disjoint intervals covering the same points as `intervals`, touching ones are merged.
+pub fn union(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.sort_unstable();
+
+    let mut merged = Vec::<Interval>::with_capacity(intervals.len());
+    for (first, last) in intervals {
+        match merged.last_mut() {
+            Some(previous) if first <= previous.1 + 1 => previous.1 = previous.1.max(last),
+            _ => merged.push((first, last)),
+        }
+    }
+
+    merged
+}
+
+/// The parts of `range` not covered by the sorted disjoint `intervals`.
+pub fn complement(intervals: &[Interval], (first, last): Interval) -> Vec<Interval> {
+    let mut gaps = Vec::new();
+    let mut next = first;
+    for &(start, end) in intervals {
+        if start > last {
+            break;
+        }
+        if start > next {
+            gaps.push((next, start - 1));
+        }
+        next = next.max(end + 1);
+    }
+    if next <= last {
+        gaps.push((next, last));
+    }
+
+    gaps
+}
+
+/// Points with `min.0 <= x <= max.0` and `min.1 <= y <= max.1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    /// The square of part B.
+    pub fn search_box(size: i64) -> Rect {
+        Rect {
+            min: (0, 0),
+            max: (size, size),
+        }
+    }
+
+    pub fn contains(&self, (x, y): Point) -> bool {
+        (self.min.0..=self.max.0).contains(&x) && (self.min.1..=self.max.1).contains(&y)
+    }
+}
+
+/// The points seen by the sensors, each sees a diamond of the points within its radius.
+#[derive(Debug, Clone)]
+pub struct SensorField {
+    sensors: Vec<(Point, i64)>,
+    beacons: Vec<Point>,
+}
+
+impl SensorField {
+    pub fn new(readings: &[Reading]) -> SensorField {
+        let sensors = readings
+            .iter()
+            .map(|reading| (reading.sensor, reading.radius()))
+            .collect();
+        let beacons = readings
+            .iter()
+            .map(|reading| reading.beacon)
+            .sorted()
+            .dedup()
+            .collect();
+
+        SensorField { sensors, beacons }
+    }
+
+    /// The known beacons, sorted and each once.
+    pub fn beacons(&self) -> &[Point] {
+        &self.beacons
+    }
+
+    /// Whether some sensor sees `p`.
+    pub fn is_covered(&self, p: Point) -> bool {
+        self.sensors
+            .iter()
+            .any(|(sensor, radius)| manhattan(sensor, &p) <= *radius)
+    }
+
+    /// The points of row `y` seen by the sensors, as sorted disjoint intervals of `x`.
+    pub fn row(&self, y: i64) -> Vec<Interval> {
+        union(
+            self.sensors
+                .iter()
+                .filter_map(|&((x, sensor_y), radius)| {
+                    let reach = radius - (y - sensor_y).abs();
+                    (reach >= 0).then_some((x - reach, x + reach))
+                })
+                .collect(),
+        )
+    }
+
+    /// How many points of row `y` cannot hold a beacon, those seen by a sensor other than the
+    /// known beacons.
+    pub fn covered_in_row(&self, y: i64) -> u64 {
+        let covered = self.row(y).iter().map(interval_len).sum::<u64>();
+        let beacons = self.beacons.iter().filter(|beacon| beacon.1 == y).count();
+
+        covered - beacons as u64
+    }
+
+    /// The points of row `y` within `xs` that no sensor sees, as sorted disjoint intervals.
+    pub fn row_gaps(&self, y: i64, xs: Interval) -> Vec<Interval> {
+        complement(&self.row(y), xs)
+    }
+
+    /// Every point of `rect` that no sensor sees, row by row. Rows are visited one at a time,
+    /// so use `gap` to find out whether there is any at all first.
+    pub fn gaps(&self, rect: Rect) -> impl Iterator<Item = Point> + '_ {
+        (rect.min.1..=rect.max.1).flat_map(move |y| {
+            self.row_gaps(y, (rect.min.0, rect.max.0))
+                .into_iter()
+                .flat_map(move |(first, last)| (first..=last).map(move |x| (x, y)))
+        })
+    }
+
+    /// Some point of `rect` that no sensor sees, without visiting its rows.
+    ///
+    /// In coordinates `u = x + y` and `v = x - y` every sensor sees a square. Of the unseen
+    /// points, the one with the smallest `u` and then `v` has its neighbours with smaller `u` or
+    /// `v` seen or outside `rect`, so it lies just past a side of some square or on an edge of
+    /// `rect` in both directions. Only the crossings of those lines are tried.
+    pub fn gap(&self, rect: Rect) -> Option<Point> {
+        let offsets = |radius: i64| [-radius - 2, -radius - 1, radius + 1, radius + 2];
+        let us = self
+            .sensors
+            .iter()
+            .flat_map(|&((x, y), radius)| offsets(radius).map(|d| x + y + d))
+            .sorted()
+            .dedup()
+            .collect::<Vec<i64>>();
+        let vs = self
+            .sensors
+            .iter()
+            .flat_map(|&((x, y), radius)| offsets(radius).map(|d| x - y + d))
+            .sorted()
+            .dedup()
+            .collect::<Vec<i64>>();
+
+        let Rect {
+            min: (min_x, min_y),
+            max: (max_x, max_y),
+        } = rect;
+        let crossings = us
+            .iter()
+            .cartesian_product(&vs)
+            .filter(|(u, v)| (*u - *v) % 2 == 0)
+            .map(|(u, v)| ((u + v) / 2, (u - v) / 2));
+        let on_edges = us.iter().chain(&vs).enumerate().flat_map(|(i, &w)| {
+            // u = x + y and v = x - y crossing the vertical and horizontal edges
+            let sign = if i < us.len() { 1 } else { -1 };
+            [
+                (min_x, sign * (w - min_x)),
+                (max_x, sign * (w - max_x)),
+                (w - sign * min_y, min_y),
+                (w - sign * max_y, max_y),
+            ]
+        });
+        let corners = [
+            (min_x, min_y),
+            (max_x, min_y),
+            (min_x, max_y),
+            (max_x, max_y),
+        ];
+
+        crossings
+            .chain(on_edges)
+            .chain(corners)
+            .find(|&p| rect.contains(p) && !self.is_covered(p))
+    }
+}
+
+/// The puzzle's tuning frequency of the only point in the search box no sensor sees.
+pub fn tuning_frequency(field: &SensorField, size: i64) -> Option<i64> {
+    let (x, y) = field.gap(Rect::search_box(size))?;
+
+    Some(x * 4_000_000 + y)
+}
+
+impl Solver for Day15 {
+    const DAY: u32 = 15;
+
+    type Input = Vec<Reading>;
+    type AnswerA = u64;
+    type AnswerB = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, parse_reading)
+    }
+
+    fn part_a(readings: &Vec<Reading>) -> Option<u64> {
+        Some(SensorField::new(readings).covered_in_row(ROW))
+    }
+
+    fn part_b(readings: &Vec<Reading>) -> Option<i64> {
+        tuning_frequency(&SensorField::new(readings), SEARCH_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::random::Random;
+
+    const EXAMPLE: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    /// A few sensors with beacons close by, all within `0..size` or near it.
+    fn random_field(random: &mut Random, size: usize) -> SensorField {
+        let readings = (0..1 + random.below(5))
+            .map(|_| {
+                let sensor = (random.below(size) as i64, random.below(size) as i64);
+                let dx = random.below(9) as i64 - 4;
+                let dy = random.below(9) as i64 - 4;
+                Reading {
+                    sensor,
+                    beacon: (sensor.0 + dx, sensor.1 + dy),
+                }
+            })
+            .collect::<Vec<Reading>>();
+
+        SensorField::new(&readings)
+    }
+
+    /// A rectangle of up to `size` points a side anywhere in `-size..size`.
+    fn random_rect(random: &mut Random, size: usize) -> Rect {
+        let min = (
+            random.below(2 * size) as i64 - size as i64,
+            random.below(2 * size) as i64 - size as i64,
+        );
+        let max = (
+            min.0 + random.below(size) as i64,
+            min.1 + random.below(size) as i64,
+        );
+
+        Rect { min, max }
+    }
+
+    fn scanned_gaps(field: &SensorField, rect: Rect) -> Vec<Point> {
+        (rect.min.1..=rect.max.1)
+            .flat_map(|y| (rect.min.0..=rect.max.0).map(move |x| (x, y)))
+            .filter(|&p| !field.is_covered(p))
+            .collect()
+    }
+
+    #[test]
+    fn example() {
+        let readings = Day15::parse(EXAMPLE).unwrap();
+        let field = SensorField::new(&readings);
+
+        assert_eq!(field.covered_in_row(10), 26);
+        assert_eq!(field.gap(Rect::search_box(20)), Some((14, 11)));
+        assert_eq!(
+            field.gaps(Rect::search_box(20)).collect::<Vec<Point>>(),
+            [(14, 11)]
+        );
+        assert_eq!(tuning_frequency(&field, 20), Some(56000011));
+    }
+
+    #[test]
+    fn rows_match_a_scan() {
+        let mut random = Random(15);
+        for _ in 0..200 {
+            let field = random_field(&mut random, 12);
+            let y = random.below(24) as i64 - 6;
+            let scanned = (-20..40)
+                .filter(|&x| field.is_covered((x, y)) && !field.beacons().contains(&(x, y)))
+                .count();
+
+            assert_eq!(
+                field.covered_in_row(y),
+                scanned as u64,
+                "row {y} of {field:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn gaps_match_a_scan() {
+        let mut random = Random(1522);
+        let mut only_on_edges = 0;
+        for _ in 0..2000 {
+            let field = random_field(&mut random, 12);
+            let rect = random_rect(&mut random, 12);
+            let scanned = scanned_gaps(&field, rect);
+
+            assert_eq!(
+                field.gaps(rect).collect::<Vec<Point>>(),
+                scanned,
+                "{rect:?} of {field:?}"
+            );
+            match field.gap(rect) {
+                Some(p) => assert!(scanned.contains(&p), "{p:?} in {rect:?} of {field:?}"),
+                None => assert!(scanned.is_empty(), "{rect:?} of {field:?}"),
+            }
+
+            let on_edge = |&(x, y): &Point| {
+                x == rect.min.0 || x == rect.max.0 || y == rect.min.1 || y == rect.max.1
+            };
+            if !scanned.is_empty() && scanned.iter().all(on_edge) {
+                only_on_edges += 1;
+            }
+        }
+
+        assert!(
+            only_on_edges > 20,
+            "{only_on_edges} cases with gaps only on edges"
+        );
+    }
+
+    #[test]
+    fn gaps_on_the_edges_are_found() {
+        let field = SensorField::new(&[Reading {
+            sensor: (7, 7),
+            beacon: (7, 10),
+        }]);
+        let rect = Rect {
+            min: (5, 5),
+            max: (9, 9),
+        };
+
+        assert_eq!(scanned_gaps(&field, rect), [(5, 5), (9, 5), (5, 9), (9, 9)]);
+        assert!(field
+            .gap(rect)
+            .is_some_and(|p| scanned_gaps(&field, rect).contains(&p)));
+
+        let rect = Rect {
+            min: (-20, 7),
+            max: (3, 7),
+        };
+        assert_eq!(
+            field.gaps(rect).collect::<Vec<Point>>(),
+            scanned_gaps(&field, rect)
+        );
+        assert_eq!(field.gap(rect).map(|p| p.1), Some(7));
+
+        let rect = Rect {
+            min: (6, 6),
+            max: (8, 8),
+        };
+        assert_eq!(field.gap(rect), None);
+        assert_eq!(field.gaps(rect).count(), 0);
+    }
+}
@@ -0,0 +1,735 @@
+use itertools::Itertools;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{BufRead, Write};
+
+use crate::shared::cli::{Cli, Options};
+use crate::shared::parse::{number, parse_lines, split_once, strip_prefix, LineError};
+use crate::shared::runner::{parse_input, Part};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day16;
+
+#[derive(Debug)]
+pub struct Valve {
+    pub name: String,
+    pub rate: u32,
+    pub tunnels: Vec<String>,
+}
+
+/// The valves worth opening together with `AA`, which is always first
+pub struct Network {
+    pub names: Vec<String>,
+    pub rates: Vec<u32>,
+    /// Shortest distances between the kept valves
+    pub distances: Vec<Vec<u32>>,
+    /// Valves on a shortest walk between the kept valves, from the first to the last one
+    pub walks: Vec<Vec<Vec<String>>>,
+}
+
+fn parse_name_and_rate(line: &str, s: &str) -> Result<(String, u32), LineError> {
+    let rest = strip_prefix(line, s, "Valve ")?;
+    let (name_part, rate_part) = split_once(line, rest, " has flow rate=")?;
+
+    Ok((name_part.to_string(), number(line, rate_part)?))
+}
+
+fn parse_tunnels<'a>(line: &str, s: &'a str) -> Result<Vec<&'a str>, LineError> {
+    let list_part = s
+        .strip_prefix(" tunnels lead to valves ")
+        .or_else(|| s.strip_prefix(" tunnel leads to valve "))
+        .ok_or_else(|| LineError::at(line, s, "expected ' tunnels lead to valves '"))?;
+
+    Ok(list_part.split(", ").collect())
+}
+
+fn parse_valves(input: &str) -> Result<Vec<Valve>, ParseError> {
+    let valves = parse_lines(input, |line| {
+        let (left_part, right_part) = split_once(line, line, ";")?;
+
+        let (name, rate) = parse_name_and_rate(line, left_part)?;
+        let tunnels = parse_tunnels(line, right_part)?;
+        Ok((name, rate, tunnels))
+    })?;
+
+    // Tunnels can lead only to valves that are listed as well
+    for ((name, _, tunnels), (i, line)) in valves.iter().zip(input.lines().enumerate()) {
+        if let Some(tunnel) = tunnels
+            .iter()
+            .find(|&tunnel| !valves.iter().any(|(other, _, _)| other == tunnel))
+        {
+            return Err(LineError::at(
+                line,
+                tunnel,
+                format!("valve {name} leads to unknown valve {tunnel}"),
+            )
+            .on_line(i + 1, line));
+        }
+    }
+
+    if !valves.iter().any(|(name, _, _)| name == "AA") {
+        return Err(ParseError::end_of_input(input, "expected the valve AA"));
+    }
+
+    Ok(valves
+        .into_iter()
+        .map(|(name, rate, tunnels)| Valve {
+            name,
+            rate,
+            tunnels: tunnels
+                .into_iter()
+                .map(|tunnel| tunnel.to_string())
+                .collect(),
+        })
+        .sorted_by_key(|valve| valve.name.clone())
+        .collect::<Vec<Valve>>())
+}
+
+/// All-pairs shortest distances between valves, `index` maps valve names to rows and columns.
+pub fn calculate_distances(valves: &[Valve], index: &HashMap<String, usize>) -> Vec<Vec<u32>> {
+    let mut distances = vec![vec![u32::MAX; valves.len()]; valves.len()];
+    for (i, valve) in valves.iter().enumerate() {
+        distances[i][i] = 0;
+        for tunnel in valve.tunnels.iter() {
+            distances[i][index[tunnel]] = 1;
+        }
+    }
+
+    for k in 0..valves.len() {
+        for i in 0..valves.len() {
+            for j in 0..valves.len() {
+                let new_dist = distances[i][k].saturating_add(distances[k][j]);
+                if distances[i][j] > new_dist {
+                    distances[i][j] = new_dist
+                }
+            }
+        }
+    }
+
+    distances
+}
+
+/// All valves with the tunnels between them and the shortest distances between every two of them,
+/// `u32::MAX` when there is no way.
+#[derive(Debug)]
+pub struct Tunnels {
+    valves: Vec<Valve>,
+    index: HashMap<String, usize>,
+    distances: Vec<Vec<u32>>,
+}
+
+impl Tunnels {
+    /// Tunnels must lead only to the given valves.
+    pub fn new(valves: Vec<Valve>) -> Tunnels {
+        let index = valves
+            .iter()
+            .enumerate()
+            .map(|(i, valve)| (valve.name.clone(), i))
+            .collect::<HashMap<String, usize>>();
+        let distances = calculate_distances(&valves, &index);
+
+        Tunnels {
+            valves,
+            index,
+            distances,
+        }
+    }
+
+    pub fn valves(&self) -> &[Valve] {
+        &self.valves
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.index.get(name).copied()
+    }
+
+    /// Rows and columns follow the order of `valves`.
+    pub fn distances(&self) -> &[Vec<u32>] {
+        &self.distances
+    }
+
+    /// Minutes it takes to walk from one valve to another, `None` for unknown valves or when
+    /// there is no way.
+    pub fn distance(&self, from: &str, to: &str) -> Option<u32> {
+        let distance = self.distances[self.index_of(from)?][self.index_of(to)?];
+        (distance != u32::MAX).then_some(distance)
+    }
+
+    /// Valves on a shortest walk from valve `from` to valve `to`, both included. Empty when there
+    /// is no walk.
+    pub fn walk(&self, from: usize, to: usize) -> Vec<String> {
+        if self.distances[from][to] == u32::MAX {
+            return Vec::new();
+        }
+
+        let mut current = from;
+        let mut walk = vec![self.valves[from].name.clone()];
+        while current != to {
+            current = self.valves[current]
+                .tunnels
+                .iter()
+                .map(|tunnel| self.index[tunnel])
+                .find(|&next| {
+                    self.distances[next][to].saturating_add(1) == self.distances[current][to]
+                })
+                .expect("A shortest walk continues through some tunnel");
+            walk.push(self.valves[current].name.clone());
+        }
+
+        walk
+    }
+
+    /// Keeps only the valves with non-zero flow rate and `AA`.
+    pub fn compress(&self) -> Network {
+        let kept = self
+            .valves
+            .iter()
+            .enumerate()
+            .filter(|(_, valve)| valve.rate > 0 || valve.name == "AA")
+            .sorted_by_key(|(_, valve)| valve.name != "AA")
+            .map(|(i, _)| i)
+            .collect::<Vec<usize>>();
+
+        Network {
+            names: kept.iter().map(|&i| self.valves[i].name.clone()).collect(),
+            rates: kept.iter().map(|&i| self.valves[i].rate).collect(),
+            distances: kept
+                .iter()
+                .map(|&from| kept.iter().map(|&to| self.distances[from][to]).collect())
+                .collect(),
+            walks: kept
+                .iter()
+                .map(|&from| kept.iter().map(|&to| self.walk(from, to)).collect())
+                .collect(),
+        }
+    }
+
+    /// Graphviz graph of the valves labelled by their flow rates. Tunnels leading both ways are
+    /// drawn as one edge, the others as arrows.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph tunnels {\n");
+        for valve in &self.valves {
+            let shape = if valve.rate > 0 {
+                "doublecircle"
+            } else {
+                "circle"
+            };
+            dot += &format!(
+                "  {} [label=\"{}\\n{}\", shape={shape}];\n",
+                valve.name, valve.name, valve.rate
+            );
+        }
+        for (i, valve) in self.valves.iter().enumerate() {
+            for tunnel in &valve.tunnels {
+                let j = self.index[tunnel];
+                let back = self.valves[j].tunnels.contains(&valve.name);
+                if !back {
+                    dot += &format!("  {} -- {} [dir=forward];\n", valve.name, tunnel);
+                } else if i < j {
+                    dot += &format!("  {} -- {};\n", valve.name, tunnel);
+                }
+            }
+        }
+        dot += "}\n";
+
+        dot
+    }
+}
+
+impl Network {
+    /// Graphviz graph of the kept valves labelled by their flow rates, with every two of them
+    /// joined by an edge labelled by the distance between them. When one-way tunnels make the
+    /// distances differ, each way gets its own arrow.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph network {\n");
+        for (name, rate) in self.names.iter().zip(&self.rates) {
+            dot += &format!("  {name} [label=\"{name}\\n{rate}\"];\n");
+        }
+        for (i, j) in (0..self.names.len()).tuple_combinations() {
+            let (there, back) = (self.distances[i][j], self.distances[j][i]);
+            if there == back && there != u32::MAX {
+                dot += &format!(
+                    "  {} -- {} [label=\"{there}\"];\n",
+                    self.names[i], self.names[j]
+                );
+                continue;
+            }
+            for (from, to, distance) in [(i, j, there), (j, i, back)] {
+                if distance != u32::MAX {
+                    dot += &format!(
+                        "  {} -- {} [label=\"{distance}\", dir=forward];\n",
+                        self.names[from], self.names[to]
+                    );
+                }
+            }
+        }
+        dot += "}\n";
+
+        dot
+    }
+}
+
+pub const PART_A_MINUTES: u32 = 30;
+pub const PART_B_MINUTES: u32 = 26;
+pub const PART_B_AGENTS: usize = 2;
+
+/// Sets of the valves to open other than `AA` as bit masks, valve `i` of the network is bit
+/// `i - 1`.
+pub type ValveSet = usize;
+
+/// The most valves other than `AA` the planner takes. It keeps a table entry for every set of
+/// valves, and with more agents it tries every subset of every set, which takes `3^n` steps.
+pub const MAX_VALVES: usize = 20;
+
+/// The network keeps more valves worth opening than `MAX_VALVES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManyValves {
+    pub valves: usize,
+}
+
+impl fmt::Display for TooManyValves {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} valves are worth opening, the planner takes at most {MAX_VALVES}",
+            self.valves
+        )
+    }
+}
+
+impl Error for TooManyValves {}
+
+impl Network {
+    /// Every set of valves to open.
+    pub fn all_valves(&self) -> Result<ValveSet, TooManyValves> {
+        let valves = self.rates.len() - 1;
+        if valves > MAX_VALVES {
+            return Err(TooManyValves { valves });
+        }
+
+        Ok((1 << valves) - 1)
+    }
+}
+
+fn visit(
+    network: &Network,
+    node: usize,
+    remaining: u32,
+    opened: ValveSet,
+    released: u32,
+    best: &mut [u32],
+) {
+    best[opened] = best[opened].max(released);
+
+    for next in 1..network.rates.len() {
+        let bit = 1 << (next - 1);
+        if opened & bit != 0 {
+            continue;
+        }
+        if let Some(left) = remaining.checked_sub(network.distances[node][next].saturating_add(1)) {
+            let released = released + network.rates[next] * left;
+            visit(network, next, left, opened | bit, released, best);
+        }
+    }
+}
+
+/// The most pressure one agent releases in `remaining` minutes from `node` opening valves of
+/// `allowed`, with the valves to open in order.
+fn best_route(
+    network: &Network,
+    node: usize,
+    remaining: u32,
+    allowed: ValveSet,
+) -> (u32, Vec<usize>) {
+    let mut best = (0, Vec::new());
+    for next in 1..network.rates.len() {
+        let bit = 1 << (next - 1);
+        if allowed & bit == 0 {
+            continue;
+        }
+        if let Some(left) = remaining.checked_sub(network.distances[node][next].saturating_add(1)) {
+            let (released, mut route) = best_route(network, next, left, allowed ^ bit);
+            let released = released + network.rates[next] * left;
+            if released > best.0 {
+                route.insert(0, next);
+                best = (released, route);
+            }
+        }
+    }
+
+    best
+}
+
+/// The most pressure one agent releases in `minutes` opening only valves of the set, for every
+/// set. Indexed by `ValveSet`.
+pub fn best_per_valves(network: &Network, minutes: u32) -> Result<Vec<u32>, TooManyValves> {
+    let valves = network.rates.len() - 1;
+    let mut best = vec![0; network.all_valves()? + 1];
+    visit(network, 0, minutes, 0, 0, &mut best);
+
+    // Opening fewer valves than allowed is fine too
+    for bit in (0..valves).map(|i| 1 << i) {
+        for set in 0..best.len() {
+            if set & bit != 0 {
+                best[set] = best[set].max(best[set ^ bit]);
+            }
+        }
+    }
+
+    Ok(best)
+}
+
+/// The most pressure `agents` working together release in `minutes`, all starting at `AA`, with
+/// the set of valves each of them opens.
+///
+/// Agents never open the same valve, so the answer splits the valves into one set per agent. For
+/// `k` agents and every set the best split is kept, trying every subset for the `k`-th agent.
+fn best_split(
+    network: &Network,
+    agents: usize,
+    minutes: u32,
+) -> Result<(u32, Vec<ValveSet>), TooManyValves> {
+    let all = network.all_valves()?;
+    if agents == 0 {
+        return Ok((0, Vec::new()));
+    }
+
+    let single = best_per_valves(network, minutes)?;
+    let mut best = single.clone();
+    // What the `k`-th agent opens in the best split of every set, for `k` from 2 on
+    let mut choices = Vec::<Vec<ValveSet>>::new();
+    for _ in 1..agents {
+        let (most, chosen) = (0..best.len())
+            .map(|set| {
+                let mut most = (best[set], 0);
+                let mut subset = set;
+                while subset > 0 {
+                    let released = single[subset] + best[set ^ subset];
+                    if released > most.0 {
+                        most = (released, subset);
+                    }
+                    subset = (subset - 1) & set;
+                }
+                most
+            })
+            .unzip();
+        best = most;
+        choices.push(chosen);
+    }
+
+    let mut set = all;
+    let mut sets = Vec::with_capacity(agents);
+    for chosen in choices.iter().rev() {
+        sets.push(chosen[set]);
+        set ^= chosen[set];
+    }
+    sets.push(set);
+    sets.reverse();
+
+    Ok((best[all], sets))
+}
+
+/// The most pressure `agents` working together release in `minutes`, all starting at `AA`.
+pub fn max_release(network: &Network, agents: usize, minutes: u32) -> Result<u32, TooManyValves> {
+    Ok(best_split(network, agents, minutes)?.0)
+}
+
+/// One valve opened as part of a `Schedule`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opening {
+    /// Agents are counted from 1
+    pub agent: usize,
+    /// The minute spent opening the valve, it releases pressure from the next minute on
+    pub minute: u32,
+    pub valve: String,
+    pub rate: u32,
+    /// From the valve the agent opened before, or from `AA`
+    pub walk: Vec<String>,
+    /// By this valve until the time is up
+    pub released: u32,
+    /// By this and all the valves opened before it
+    pub total: u32,
+}
+
+/// Which valve is opened at which minute by which agent in the best plan, ordered by minute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    pub agents: usize,
+    pub minutes: u32,
+    pub openings: Vec<Opening>,
+}
+
+impl Schedule {
+    pub fn released(&self) -> u32 {
+        self.openings.last().map_or(0, |opening| opening.total)
+    }
+}
+
+/// The best plan for `agents` working together for `minutes`, see `max_release`.
+pub fn plan(network: &Network, agents: usize, minutes: u32) -> Result<Schedule, TooManyValves> {
+    let (_, sets) = best_split(network, agents, minutes)?;
+
+    let mut openings = Vec::new();
+    for (agent, &set) in sets.iter().enumerate() {
+        let (_, route) = best_route(network, 0, minutes, set);
+        let (mut node, mut remaining) = (0, minutes);
+        for next in route {
+            remaining -= network.distances[node][next] + 1;
+            openings.push(Opening {
+                agent: agent + 1,
+                minute: minutes - remaining,
+                valve: network.names[next].clone(),
+                rate: network.rates[next],
+                walk: network.walks[node][next].clone(),
+                released: network.rates[next] * remaining,
+                total: 0,
+            });
+            node = next;
+        }
+    }
+
+    openings.sort_by_key(|opening| (opening.minute, opening.agent));
+    let mut total = 0;
+    for opening in openings.iter_mut() {
+        total += opening.released;
+        opening.total = total;
+    }
+
+    Ok(Schedule {
+        agents,
+        minutes,
+        openings,
+    })
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>6}  {:>5}  {:<5}  {:>4}  {:>8}  {:>8}  Walk",
+            "Minute", "Agent", "Valve", "Rate", "Released", "Total"
+        )?;
+        for opening in &self.openings {
+            writeln!(
+                f,
+                "{:>6}  {:>5}  {:<5}  {:>4}  {:>8}  {:>8}  {}",
+                opening.minute,
+                opening.agent,
+                opening.valve,
+                opening.rate,
+                opening.released,
+                opening.total,
+                opening.walk.join(" -> ")
+            )?;
+        }
+        write!(
+            f,
+            "Released {} in {} minutes",
+            self.released(),
+            self.minutes
+        )
+    }
+}
+
+impl From<&Schedule> for Value {
+    fn from(schedule: &Schedule) -> Value {
+        let openings = schedule
+            .openings
+            .iter()
+            .map(|opening| {
+                json!({
+                    "agent": opening.agent,
+                    "minute": opening.minute,
+                    "valve": opening.valve,
+                    "rate": opening.rate,
+                    "walk": opening.walk,
+                    "released": opening.released,
+                    "total": opening.total,
+                })
+            })
+            .collect::<Vec<Value>>();
+
+        json!({
+            "agents": schedule.agents,
+            "minutes": schedule.minutes,
+            "released": schedule.released(),
+            "openings": openings,
+        })
+    }
+}
+
+/// `--agents <n>` and `--minutes <n>` change how many agents open valves and for how long, the
+/// part picks the defaults. `--schedule` prints the best plan as a table below the answer and
+/// `--json` prints it as JSON. `--dot <file>` saves the tunnels as a Graphviz graph, or only the
+/// valves worth opening with `--compressed`.
+pub const CLI: Cli = Cli {
+    flags: &["--schedule", "--json", "--compressed"],
+    valued: &["--agents", "--minutes", "--dot"],
+    run,
+};
+
+fn run(
+    part: Part,
+    options: &Options,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let tunnels = parse_input::<Day16>(input)?;
+    let network = tunnels.compress();
+    let (agents, minutes) = match part {
+        Part::A => (1, PART_A_MINUTES),
+        Part::B => (PART_B_AGENTS, PART_B_MINUTES),
+    };
+    let agents = options.parsed::<usize>("--agents")?.unwrap_or(agents);
+    let minutes = options.parsed::<u32>("--minutes")?.unwrap_or(minutes);
+
+    if let Some(file) = options.value("--dot") {
+        let dot = if options.flag("--compressed") {
+            network.to_dot()
+        } else {
+            tunnels.to_dot()
+        };
+        std::fs::write(file, dot).map_err(|e| format!("Cannot write {file}: {e}"))?;
+    }
+
+    if !options.flag("--schedule") && !options.flag("--json") {
+        writeln!(output, "{}", max_release(&network, agents, minutes)?)?;
+        return Ok(());
+    }
+
+    let schedule = plan(&network, agents, minutes)?;
+    writeln!(output, "{}", schedule.released())?;
+    if options.flag("--schedule") {
+        writeln!(output, "{schedule}")?;
+    }
+    if options.flag("--json") {
+        writeln!(output, "{:#}", Value::from(&schedule))?;
+    }
+
+    Ok(())
+}
+
+impl Solver for Day16 {
+    const DAY: u32 = 16;
+
+    type Input = Tunnels;
+    type AnswerA = u32;
+    type AnswerB = u32;
+
+    fn parse(input: &str) -> Result<Tunnels, ParseError> {
+        Ok(Tunnels::new(parse_valves(input)?))
+    }
+
+    fn part_a(tunnels: &Tunnels) -> Option<u32> {
+        max_release(&tunnels.compress(), 1, PART_A_MINUTES).ok()
+    }
+
+    fn part_b(tunnels: &Tunnels) -> Option<u32> {
+        max_release(&tunnels.compress(), PART_B_AGENTS, PART_B_MINUTES).ok()
+    }
+
+    fn try_part_a(tunnels: &Tunnels) -> Result<Option<u32>, Box<dyn Error>> {
+        Ok(Some(max_release(&tunnels.compress(), 1, PART_A_MINUTES)?))
+    }
+
+    fn try_part_b(tunnels: &Tunnels) -> Result<Option<u32>, Box<dyn Error>> {
+        Ok(Some(max_release(
+            &tunnels.compress(),
+            PART_B_AGENTS,
+            PART_B_MINUTES,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    /// `AA` followed by a line of `valves` valves of rate 1.
+    fn line(valves: usize) -> Network {
+        let names = (0..=valves)
+            .map(|i| {
+                if i == 0 {
+                    "AA".to_string()
+                } else {
+                    format!("V{i:02}")
+                }
+            })
+            .collect::<Vec<String>>();
+        let input = (0..=valves)
+            .map(|i| {
+                let tunnels = [i.checked_sub(1), Some(i + 1).filter(|&j| j <= valves)]
+                    .into_iter()
+                    .flatten()
+                    .map(|j| names[j].as_str())
+                    .collect::<Vec<&str>>();
+                format!(
+                    "Valve {} has flow rate={}; tunnels lead to valves {}",
+                    names[i],
+                    (i > 0) as u32,
+                    tunnels.join(", ")
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        Day16::parse(&input).unwrap().compress()
+    }
+
+    #[test]
+    fn example_for_any_number_of_agents() {
+        let network = Day16::parse(EXAMPLE).unwrap().compress();
+
+        assert_eq!(max_release(&network, 1, 30), Ok(1651));
+        assert_eq!(max_release(&network, 2, 26), Ok(1707));
+        assert_eq!(max_release(&network, 0, 26), Ok(0));
+        assert_eq!(plan(&network, 2, 26).unwrap().released(), 1707);
+    }
+
+    #[test]
+    fn valves_up_to_the_limit_are_planned() {
+        assert_eq!(line(MAX_VALVES).all_valves(), Ok((1 << MAX_VALVES) - 1));
+        assert!(max_release(&line(12), 2, 10).is_ok());
+    }
+
+    #[test]
+    fn too_many_valves_are_reported() {
+        let error = TooManyValves { valves: 70 };
+
+        assert_eq!(line(70).all_valves(), Err(error));
+        assert_eq!(max_release(&line(70), 1, 30), Err(error));
+        assert_eq!(plan(&line(70), 2, 26).err(), Some(error));
+    }
+
+    #[test]
+    fn options_default_to_the_part() {
+        assert_eq!(CLI.run_on(Part::A, &[], EXAMPLE).unwrap(), "1651\n");
+        assert_eq!(CLI.run_on(Part::B, &[], EXAMPLE).unwrap(), "1707\n");
+        assert_eq!(
+            CLI.run_on(Part::B, &["--agents", "1", "--minutes", "30"], EXAMPLE)
+                .unwrap(),
+            "1651\n"
+        );
+
+        let output = CLI.run_on(Part::A, &["--json"], EXAMPLE).unwrap();
+        let (released, json) = output.split_once('\n').unwrap();
+        assert_eq!(released, "1651");
+        let json = serde_json::from_str::<Value>(json).unwrap();
+        assert_eq!(json["agents"], 1);
+        assert_eq!(json["released"], 1651);
+    }
+}
@@ -0,0 +1,497 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{BufRead, Write};
+
+use crate::shared::cli::{Cli, Options};
+use crate::shared::runner::{parse_input, Part};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day17;
+
+/// The rocks of the puzzle in the order they fall, drawn like in `parse_rocks`.
+pub const ROCKS: &str = "\
+####
+
+.#.
+###
+.#.
+
+..#
+..#
+###
+
+#
+#
+#
+#
+
+##
+##
+";
+
+pub const WIDTH: u32 = 7;
+pub const PART_A_ROCKS: u64 = 2022;
+pub const PART_B_ROCKS: u64 = 1_000_000_000_000;
+
+/// A rock shape as one bit mask per row from the bottom up, bit `x` is column `x` from the left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rock {
+    rows: Vec<u64>,
+    width: u32,
+}
+
+impl Rock {
+    /// Points of the shape as `(x, y)`, `y` grows upwards. Empty rows and columns on the bottom
+    /// and the left are dropped, `None` when there are no points or the shape is wider than 64.
+    pub fn from_points(points: &[(u32, u32)]) -> Option<Rock> {
+        let min_x = points.iter().map(|p| p.0).min()?;
+        let min_y = points.iter().map(|p| p.1).min()?;
+        let width = points.iter().map(|p| p.0 - min_x + 1).max()?;
+        let height = points.iter().map(|p| p.1 - min_y + 1).max()?;
+        if width > u64::BITS {
+            return None;
+        }
+
+        let mut rows = vec![0u64; height as usize];
+        for &(x, y) in points {
+            rows[(y - min_y) as usize] |= 1 << (x - min_x);
+        }
+
+        Some(Rock { rows, width })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+/// Rocks drawn with `#` and `.` from the top down, separated by empty lines.
+pub fn parse_rocks(text: &str) -> Result<Vec<Rock>, ParseError> {
+    let lines = text.lines().enumerate().collect::<Vec<(usize, &str)>>();
+
+    lines
+        .split(|(_, line)| line.trim().is_empty())
+        .filter(|drawing| !drawing.is_empty())
+        .map(|drawing| {
+            let mut points = Vec::new();
+            for (row, &(i, line)) in drawing.iter().rev().enumerate() {
+                for (x, c) in line.chars().enumerate() {
+                    match c {
+                        '#' => points.push((x as u32, row as u32)),
+                        '.' => (),
+                        _ => {
+                            return Err(ParseError::new(
+                                i + 1,
+                                x + 1,
+                                line,
+                                format!("expected '#' or '.', found '{c}'"),
+                            ))
+                        }
+                    }
+                }
+            }
+
+            let (i, line) = drawing[0];
+            Rock::from_points(&points)
+                .ok_or_else(|| ParseError::new(i + 1, 1, line, "expected a rock at most 64 wide"))
+        })
+        .collect()
+}
+
+/// The tower of settled rocks, one bit mask per row from the floor up.
+#[derive(Debug, Clone)]
+pub struct Chamber {
+    width: u32,
+    rows: Vec<u64>,
+}
+
+impl Chamber {
+    /// `None` unless `width` is between 1 and 64.
+    pub fn new(width: u32) -> Option<Chamber> {
+        (1..=u64::BITS).contains(&width).then(|| Chamber {
+            width,
+            rows: Vec::new(),
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the tower, rows above are always empty.
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Up to `count` rows from the top of the tower down.
+    pub fn top(&self, count: usize) -> &[u64] {
+        &self.rows[self.rows.len().saturating_sub(count)..]
+    }
+
+    /// Up to `depth` rows from the top of the tower down to the first one falling rock cannot
+    /// reach below, or to the floor. Rock moves one point left, right or down at a time through
+    /// empty points, so nothing below that row ever changes.
+    pub fn surface(&self, depth: usize) -> &[u64] {
+        let full = u64::MAX >> (u64::BITS - self.width);
+        let mut reached = full;
+        for (row_depth, &row) in self.rows.iter().rev().take(depth).enumerate() {
+            let free = !row & full;
+            reached &= free;
+            loop {
+                let spread = (reached | reached << 1 | reached >> 1) & free;
+                if spread == reached {
+                    break;
+                }
+                reached = spread;
+            }
+            if reached == 0 {
+                return self.top(row_depth + 1);
+            }
+        }
+
+        self.top(depth)
+    }
+
+    /// Whether `rock` with its bottom left corner at `(x, y)` stays within the walls and touches
+    /// no settled rock.
+    pub fn fits(&self, rock: &Rock, x: u32, y: usize) -> bool {
+        x + rock.width <= self.width
+            && rock
+                .rows
+                .iter()
+                .zip(self.rows.iter().skip(y))
+                .all(|(rock_row, row)| row & (rock_row << x) == 0)
+    }
+
+    fn settle(&mut self, rock: &Rock, x: u32, y: usize) {
+        if self.rows.len() < y + rock.height() {
+            self.rows.resize(y + rock.height(), 0);
+        }
+        for (row, rock_row) in self.rows[y..].iter_mut().zip(&rock.rows) {
+            *row |= rock_row << x;
+        }
+    }
+
+    /// Lets `rock` fall from two columns right of the left wall and three rows above the tower,
+    /// pushed by the jets from `jet` on, and returns where its bottom left corner settles. `jet`
+    /// is left at the next jet to use. The rock must fit between the walls and `jets` must not be
+    /// empty.
+    pub fn drop_rock(&mut self, rock: &Rock, jets: &[char], jet: &mut usize) -> (u32, usize) {
+        assert!(
+            rock.width <= self.width,
+            "The rock is wider than the chamber"
+        );
+
+        let mut x = 2.min(self.width - rock.width);
+        let mut y = self.height() + 3;
+        loop {
+            let pushed = match jets[*jet] {
+                '<' => x.checked_sub(1),
+                _ => Some(x + 1),
+            };
+            *jet = (*jet + 1) % jets.len();
+            if let Some(pushed) = pushed.filter(|&pushed| self.fits(rock, pushed, y)) {
+                x = pushed;
+            }
+
+            if y == 0 || !self.fits(rock, x, y - 1) {
+                self.settle(rock, x, y);
+                return (x, y);
+            }
+            y -= 1;
+        }
+    }
+}
+
+impl fmt::Display for Chamber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows.iter().rev() {
+            let cells = (0..self.width)
+                .map(|x| if row >> x & 1 == 1 { '#' } else { '.' })
+                .collect::<String>();
+            writeln!(f, "|{cells}|")?;
+        }
+        write!(f, "+{}+", "-".repeat(self.width as usize))
+    }
+}
+
+/// The height of the tower after `count` rocks taken from `rocks` in turn fall into a chamber
+/// `width` wide. The rocks that fall between two times the same rock and jet meet the same
+/// `Chamber::surface`, as deep as all rocks stacked twice, repeat and are skipped once they have
+/// done so twice with the same growth. `None` without jets or rocks, for a width outside 1 to 64,
+/// when a rock is wider than the chamber or when the height overflows.
+pub fn tower_height(jets: &[char], rocks: &[Rock], width: u32, count: u64) -> Option<u64> {
+    let mut chamber = Chamber::new(width)?;
+    if jets.is_empty() || rocks.is_empty() || rocks.iter().any(|rock| rock.width > width) {
+        return None;
+    }
+
+    let depth = 2 * rocks.iter().map(Rock::height).sum::<usize>();
+    let mut seen = HashMap::<(usize, usize, Vec<u64>), (u64, usize, Option<(u64, usize)>)>::new();
+    let mut skipped = None;
+    let mut jet = 0;
+    let mut dropped = 0u64;
+    while dropped < count {
+        let rock = (dropped % rocks.len() as u64) as usize;
+        chamber.drop_rock(&rocks[rock], jets, &mut jet);
+        dropped += 1;
+
+        if skipped.is_none() {
+            let key = (
+                (rock + 1) % rocks.len(),
+                jet,
+                chamber.surface(depth).to_vec(),
+            );
+            let (before, height, repeat) =
+                seen.entry(key).or_insert((dropped, chamber.height(), None));
+            if *before == dropped {
+                continue;
+            }
+
+            let period = (dropped - *before, chamber.height() - *height);
+            if *repeat == Some(period) {
+                let cycles = (count - dropped) / period.0;
+                skipped = Some(cycles.checked_mul(period.1 as u64)?);
+                dropped += cycles * period.0;
+            }
+            (*before, *height, *repeat) = (dropped, chamber.height(), Some(period));
+        }
+    }
+
+    (chamber.height() as u64).checked_add(skipped.unwrap_or(0))
+}
+
+/// The rocks of the puzzle.
+pub fn puzzle_rocks() -> Vec<Rock> {
+    parse_rocks(ROCKS).expect("The puzzle rocks are valid")
+}
+
+/// The most rocks `--draw` lets fall, drawing skips no repeats.
+const DRAW_LIMIT: u64 = 100_000;
+
+/// `--rocks <file>` loads the rocks drawn with `#` and `.` and separated by empty lines,
+/// `--width <n>` changes the width of the chamber up to 64 and `--count <n>` how many rocks fall,
+/// by default as many as the part asks for. `--draw` prints the tower below the answer, without
+/// skipping the repeating part.
+pub const CLI: Cli = Cli {
+    flags: &["--draw"],
+    valued: &["--rocks", "--width", "--count"],
+    run,
+};
+
+fn run(
+    part: Part,
+    options: &Options,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let jets = parse_input::<Day17>(input)?;
+    let rocks = match options.value("--rocks") {
+        Some(file) => {
+            let text =
+                std::fs::read_to_string(file).map_err(|e| format!("Cannot read {file}: {e}"))?;
+            parse_rocks(&text).map_err(|e| format!("{file}: {e}"))?
+        }
+        None => puzzle_rocks(),
+    };
+    let width = options.parsed::<u32>("--width")?.unwrap_or(WIDTH);
+    let count = match part {
+        Part::A => PART_A_ROCKS,
+        Part::B => PART_B_ROCKS,
+    };
+    let count = options.parsed::<u64>("--count")?.unwrap_or(count);
+
+    if rocks.is_empty() {
+        return Err("There are no rocks".into());
+    }
+    let mut chamber = Chamber::new(width).ok_or("The width must be between 1 and 64")?;
+    if let Some(rock) = rocks.iter().position(|rock| rock.width() > width) {
+        return Err(format!("Rock {} is wider than the chamber", rock + 1).into());
+    }
+
+    let height = tower_height(&jets, &rocks, width, count).ok_or("The input has no answer")?;
+    writeln!(output, "{height}")?;
+
+    if options.flag("--draw") {
+        if count > DRAW_LIMIT {
+            return Err(format!("Drawing is limited to {DRAW_LIMIT} rocks").into());
+        }
+        let mut jet = 0;
+        for rock in rocks.iter().cycle().take(count as usize) {
+            chamber.drop_rock(rock, &jets, &mut jet);
+        }
+        writeln!(output, "{chamber}")?;
+    }
+
+    Ok(())
+}
+
+impl Solver for Day17 {
+    const DAY: u32 = 17;
+
+    type Input = Vec<char>;
+    type AnswerA = u64;
+    type AnswerB = u64;
+
+    fn parse(input: &str) -> Result<Vec<char>, ParseError> {
+        let line = input
+            .lines()
+            .next()
+            .filter(|line| !line.is_empty())
+            .ok_or_else(|| ParseError::end_of_input(input, "expected the jet pattern"))?;
+
+        line.chars()
+            .enumerate()
+            .map(|(i, c)| match c {
+                '<' | '>' => Ok(c),
+                _ => Err(ParseError::new(
+                    1,
+                    i + 1,
+                    line,
+                    format!("expected '<' or '>', found '{c}'"),
+                )),
+            })
+            .collect()
+    }
+
+    fn part_a(jets: &Vec<char>) -> Option<u64> {
+        tower_height(jets, &puzzle_rocks(), WIDTH, PART_A_ROCKS)
+    }
+
+    fn part_b(jets: &Vec<char>) -> Option<u64> {
+        tower_height(jets, &puzzle_rocks(), WIDTH, PART_B_ROCKS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::random::Random;
+
+    const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n";
+
+    /// The height after `count` rocks without skipping anything.
+    fn simulated_height(jets: &[char], rocks: &[Rock], width: u32, count: u64) -> u64 {
+        let mut chamber = Chamber::new(width).unwrap();
+        let mut jet = 0;
+        for rock in rocks.iter().cycle().take(count as usize) {
+            chamber.drop_rock(rock, jets, &mut jet);
+        }
+
+        chamber.height() as u64
+    }
+
+    #[test]
+    fn example() {
+        let jets = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(Day17::part_a(&jets), Some(3068));
+        assert_eq!(Day17::part_b(&jets), Some(1514285714288));
+        assert_eq!(
+            simulated_height(&jets, &puzzle_rocks(), WIDTH, PART_A_ROCKS),
+            3068
+        );
+    }
+
+    #[test]
+    fn repeats_are_skipped_in_any_chamber() {
+        let jets = Day17::parse(EXAMPLE).unwrap();
+        let tall = parse_rocks(&format!("{}###", "..#\n".repeat(39))).unwrap();
+        let mut random = Random(17);
+        let jagged = (0..6)
+            .map(|_| {
+                let points = (0..6)
+                    .map(|_| (random.below(4) as u32, random.below(50) as u32))
+                    .collect::<Vec<(u32, u32)>>();
+                Rock::from_points(&points).unwrap()
+            })
+            .collect::<Vec<Rock>>();
+
+        for (rocks, width) in [
+            (puzzle_rocks(), 64),
+            (puzzle_rocks(), 1 + 3),
+            (tall, WIDTH),
+            (jagged, 5),
+        ] {
+            let width = width.max(rocks.iter().map(Rock::width).max().unwrap());
+            for count in [0, 1, 1000, 3000] {
+                assert_eq!(
+                    tower_height(&jets, &rocks, width, count),
+                    Some(simulated_height(&jets, &rocks, width, count)),
+                    "{count} rocks in a chamber {width} wide"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn surface_ends_below_the_reach_of_falling_rock() {
+        let mut chamber = Chamber::new(4).unwrap();
+        assert!(chamber.surface(10).is_empty());
+
+        chamber.settle(&parse_rocks("####").unwrap()[0], 0, 0);
+        chamber.settle(&parse_rocks("#..#\n#.##").unwrap()[0], 0, 1);
+        assert_eq!(chamber.surface(10), [0b1111, 0b1101, 0b1001]);
+        assert_eq!(chamber.surface(2), [0b1101, 0b1001]);
+
+        chamber.settle(&parse_rocks("##").unwrap()[0], 1, 3);
+        assert_eq!(chamber.surface(10), [0b1001, 0b0110]);
+    }
+
+    #[test]
+    fn no_jets_have_no_answer() {
+        assert_eq!(tower_height(&[], &puzzle_rocks(), WIDTH, 10), None);
+        assert_eq!(tower_height(&['<'], &[], WIDTH, 10), None);
+        assert_eq!(tower_height(&['<'], &puzzle_rocks(), 65, 10), None);
+        assert_eq!(tower_height(&['<'], &puzzle_rocks(), 3, 10), None);
+        assert!(Day17::parse("").is_err());
+    }
+
+    #[test]
+    fn rocks_are_drawn_from_the_top_down() {
+        let rocks = parse_rocks("\n.#\n##\n\n\n#..#\n").unwrap();
+        assert_eq!(rocks.len(), 2);
+        assert_eq!(
+            rocks[0],
+            Rock::from_points(&[(1, 1), (0, 0), (1, 0)]).unwrap()
+        );
+        assert_eq!((rocks[1].width(), rocks[1].height()), (4, 1));
+        assert_eq!(parse_rocks(ROCKS).unwrap().len(), 5);
+        assert_eq!(parse_rocks("\n\n").unwrap(), []);
+    }
+
+    #[test]
+    fn rock_errors_point_at_the_drawing() {
+        let e = parse_rocks("##\n\n#.\n#o\n").unwrap_err();
+        assert_eq!((e.line, e.column), (4, 2));
+        assert_eq!(e.message, "expected '#' or '.', found 'o'");
+
+        let e = parse_rocks("##\n\n....\n..\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+        assert_eq!(e.message, "expected a rock at most 64 wide");
+
+        let wide = format!("#\n\n#{}#\n", ".".repeat(63));
+        let e = parse_rocks(&wide).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+    }
+
+    #[test]
+    fn options_default_to_the_part() {
+        assert_eq!(CLI.run_on(Part::A, &[], EXAMPLE).unwrap(), "3068\n");
+        assert_eq!(
+            CLI.run_on(Part::B, &[], EXAMPLE).unwrap(),
+            "1514285714288\n"
+        );
+        assert_eq!(
+            CLI.run_on(Part::B, &["--count", "3", "--draw"], EXAMPLE)
+                .unwrap(),
+            "6\n|..#....|\n|..#....|\n|####...|\n|..###..|\n|...#...|\n|..####.|\n+-------+\n"
+        );
+        assert!(CLI.run_on(Part::B, &["--draw"], EXAMPLE).is_err());
+        assert!(CLI.run_on(Part::A, &["--width", "3"], EXAMPLE).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+
+use crate::shared::parse::{number, parse_lines, LineError};
+use crate::shared::search::reachable;
+use crate::shared::{ParseError, Solver};
+
+pub struct Day18;
+
+pub type PointSet = HashSet<(i32, i32, i32)>;
+
+const NEIGHBORS: [(i32, i32, i32); 6] = [
+    (0, 0, -1),
+    (0, 0, 1),
+    (1, 0, 0),
+    (0, 1, 0),
+    (-1, 0, 0),
+    (0, -1, 0),
+];
+
+fn add((x1, y1, z1): &(i32, i32, i32), (x2, y2, z2): &(i32, i32, i32)) -> (i32, i32, i32) {
+    (x1 + x2, y1 + y2, z1 + z2)
+}
+
+type Bounds = ((i32, i32), (i32, i32), (i32, i32));
+
+fn within_bounds(
+    (x, y, z): &(i32, i32, i32),
+    ((min_x, max_x), (min_y, max_y), (min_z, max_z)): &Bounds,
+) -> bool {
+    x >= min_x && x <= max_x && y >= min_y && y <= max_y && z >= min_z && z <= max_z
+}
+
+fn get_bounds(points: &PointSet) -> Option<Bounds> {
+    let range = |coord: fn(&(i32, i32, i32)) -> i32| {
+        Some((
+            points.iter().map(coord).min()?,
+            points.iter().map(coord).max()?,
+        ))
+    };
+
+    Some((range(|p| p.0)?, range(|p| p.1)?, range(|p| p.2)?))
+}
+
+impl Solver for Day18 {
+    const DAY: u32 = 18;
+
+    type Input = PointSet;
+    type AnswerA = usize;
+    type AnswerB = usize;
+
+    fn parse(input: &str) -> Result<PointSet, ParseError> {
+        let points = parse_lines(input, |line| {
+            let parts = line.trim().split(',').collect::<Vec<&str>>();
+            match parts[..] {
+                [x, y, z] => Ok((number(line, x)?, number(line, y)?, number(line, z)?)),
+                _ => Err(LineError::new(
+                    1,
+                    "expected three coordinates separated by ','",
+                )),
+            }
+        })?;
+
+        Ok(PointSet::from_iter(points))
+    }
+
+    fn part_a(points: &PointSet) -> Option<usize> {
+        let area = points
+            .iter()
+            .map(|p| {
+                NEIGHBORS
+                    .iter()
+                    .filter(|&n| !points.contains(&add(p, n)))
+                    .count()
+            })
+            .sum::<usize>();
+
+        Some(area)
+    }
+
+    fn part_b(droplet: &PointSet) -> Option<usize> {
+        // Air around the droplet, one unit of margin lets it flow around every side
+        let ((min_x, max_x), (min_y, max_y), (min_z, max_z)) = get_bounds(droplet)?;
+        let bounds = (
+            (min_x - 1, max_x + 1),
+            (min_y - 1, max_y + 1),
+            (min_z - 1, max_z + 1),
+        );
+
+        let true_exterior = reachable([(min_x - 1, min_y - 1, min_z - 1)], |p| {
+            NEIGHBORS
+                .iter()
+                .map(|n| add(p, n))
+                .filter(|np| within_bounds(np, &bounds) && !droplet.contains(np))
+                .collect::<Vec<(i32, i32, i32)>>()
+        });
+
+        let exterior_area = droplet
+            .iter()
+            .map(|p| {
+                NEIGHBORS
+                    .iter()
+                    .filter(|&n| true_exterior.contains(&add(p, n)))
+                    .count()
+            })
+            .sum::<usize>();
+
+        Some(exterior_area)
+    }
+}
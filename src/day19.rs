@@ -0,0 +1,260 @@
+use cgmath::Vector4;
+use priority_queue::PriorityQueue;
+
+use crate::shared::parse::{number, parse_lines, split_once, strip_prefix, LineError};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day19;
+
+/// Robot costs as (ore, clay, obsidian, geode) vectors
+pub struct Blueprint {
+    pub ore_cost: Vector4<u32>,
+    pub clay_cost: Vector4<u32>,
+    pub obsidian_cost: Vector4<u32>,
+    pub geode_cost: Vector4<u32>,
+    pub max_cost: Vector4<u32>,
+}
+
+#[derive(Hash, PartialEq, Eq)]
+struct State {
+    resources: Vector4<u32>,
+    robots: Vector4<u32>,
+    remaining: u32,
+}
+
+#[derive(PartialEq, Eq)]
+struct CustomPriority {
+    v: Vector4<u32>,
+}
+
+impl From<Vector4<u32>> for CustomPriority {
+    fn from(v: Vector4<u32>) -> Self {
+        CustomPriority { v }
+    }
+}
+
+impl Ord for CustomPriority {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match self.v.w.cmp(&other.v.w) {
+            std::cmp::Ordering::Less => std::cmp::Ordering::Less,
+            std::cmp::Ordering::Greater => std::cmp::Ordering::Greater,
+            std::cmp::Ordering::Equal => match self.v.z.cmp(&other.v.z) {
+                std::cmp::Ordering::Less => std::cmp::Ordering::Less,
+                std::cmp::Ordering::Greater => std::cmp::Ordering::Greater,
+                std::cmp::Ordering::Equal => match self.v.y.cmp(&other.v.y) {
+                    std::cmp::Ordering::Less => std::cmp::Ordering::Less,
+                    std::cmp::Ordering::Greater => std::cmp::Ordering::Greater,
+                    std::cmp::Ordering::Equal => match self.v.x.cmp(&other.v.x) {
+                        std::cmp::Ordering::Less => std::cmp::Ordering::Less,
+                        std::cmp::Ordering::Greater => std::cmp::Ordering::Greater,
+                        std::cmp::Ordering::Equal => std::cmp::Ordering::Equal,
+                    },
+                },
+            },
+        }
+    }
+}
+
+impl PartialOrd for CustomPriority {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn can_afford_now(cost: &Vector4<u32>, resources: &Vector4<u32>) -> bool {
+    cost.x <= resources.x && cost.y <= resources.y && cost.z <= resources.z && cost.w <= resources.w
+}
+fn can_afford_after(
+    cost: &Vector4<u32>,
+    resources: &Vector4<u32>,
+    robots: &Vector4<u32>,
+) -> Option<u32> {
+    if can_afford_now(cost, resources) {
+        return Some(0);
+    }
+
+    let x_wait;
+    let y_wait;
+    let z_wait;
+    let w_wait;
+    if cost.x > 0 && robots.x == 0 {
+        return None;
+    } else if cost.x == 0 {
+        x_wait = 0.0;
+    } else {
+        x_wait = f32::ceil((cost.x as f32 - resources.x as f32) / robots.x as f32);
+    }
+    if cost.y > 0 && robots.y == 0 {
+        return None;
+    } else if cost.y == 0 {
+        y_wait = 0.0;
+    } else {
+        y_wait = f32::ceil((cost.y as f32 - resources.y as f32) / robots.y as f32);
+    }
+    if cost.z > 0 && robots.z == 0 {
+        return None;
+    } else if cost.z == 0 {
+        z_wait = 0.0;
+    } else {
+        z_wait = f32::ceil((cost.z as f32 - resources.z as f32) / robots.z as f32);
+    }
+    if cost.w > 0 && robots.w == 0 {
+        return None;
+    } else if cost.w == 0 {
+        w_wait = 0.0;
+    } else {
+        w_wait = f32::ceil((cost.w as f32 - resources.w as f32) / robots.w as f32);
+    }
+
+    Some(0.0f32.max(x_wait.max(y_wait.max(z_wait.max(w_wait)))) as u32)
+}
+
+pub fn max_geodes(b: &Blueprint, time_limit: u32) -> u32 {
+    let mut candidate = 0u32;
+    let mut queue = PriorityQueue::<State, CustomPriority>::new();
+    queue.push(
+        State {
+            resources: Vector4::new(0, 0, 0, 0),
+            robots: Vector4::new(1, 0, 0, 0),
+            remaining: time_limit,
+        },
+        Vector4::new(time_limit, 0, 0, 0).into(),
+    );
+    while let Some((s, p)) = queue.pop() {
+        if s.remaining > 2
+            && s.resources.w + s.robots.w * s.remaining + (s.remaining - 1) * (s.remaining - 2)
+                < candidate
+        {
+            // If the current upper bound on geode making potential is smaller than current
+            // candidate then continue
+            continue;
+        }
+
+        if s.remaining == 0 {
+            if s.resources.w > candidate {
+                candidate = s.resources.w;
+            }
+            continue;
+        }
+        if let Some(delay) = can_afford_after(&b.geode_cost, &s.resources, &s.robots) {
+            if s.remaining > delay {
+                queue.push(
+                    State {
+                        resources: s.resources + (delay + 1) * s.robots - b.geode_cost,
+                        robots: s.robots + Vector4::unit_w(),
+                        remaining: s.remaining - delay - 1,
+                    },
+                    (p.v + Vector4::unit_w() * (s.remaining - delay - 1)).into(),
+                );
+            }
+        }
+        if s.robots.z < b.max_cost.z {
+            if let Some(delay) = can_afford_after(&b.obsidian_cost, &s.resources, &s.robots) {
+                if s.remaining > delay {
+                    queue.push(
+                        State {
+                            resources: s.resources + (delay + 1) * s.robots - b.obsidian_cost,
+                            robots: s.robots + Vector4::unit_z(),
+                            remaining: s.remaining - delay - 1,
+                        },
+                        (p.v + Vector4::unit_z() * (s.remaining - delay - 1)).into(),
+                    );
+                }
+            }
+        }
+        if s.robots.y < b.max_cost.y {
+            if let Some(delay) = can_afford_after(&b.clay_cost, &s.resources, &s.robots) {
+                if s.remaining > delay {
+                    queue.push(
+                        State {
+                            resources: s.resources + (delay + 1) * s.robots - b.clay_cost,
+                            robots: s.robots + Vector4::unit_y(),
+                            remaining: s.remaining - delay - 1,
+                        },
+                        (p.v + Vector4::unit_y() * (s.remaining - delay - 1)).into(),
+                    );
+                }
+            }
+        }
+        if s.robots.x < b.max_cost.x {
+            if let Some(delay) = can_afford_after(&b.ore_cost, &s.resources, &s.robots) {
+                if s.remaining > delay {
+                    queue.push(
+                        State {
+                            resources: s.resources + (delay + 1) * s.robots - b.ore_cost,
+                            robots: s.robots + Vector4::unit_x(),
+                            remaining: s.remaining - delay - 1,
+                        },
+                        (p.v + Vector4::unit_x() * (s.remaining - delay - 1)).into(),
+                    );
+                }
+            }
+        }
+    }
+    candidate
+}
+
+fn parse_blueprint(line: &str) -> Result<Blueprint, LineError> {
+    let rest = strip_prefix(line, line, "Blueprint ")?;
+    let (id, rest) = split_once(line, rest, ": Each ore robot costs ")?;
+    number::<u32>(line, id)?;
+
+    let (ore_ore, rest) = split_once(line, rest, " ore. Each clay robot costs ")?;
+    let (clay_ore, rest) = split_once(line, rest, " ore. Each obsidian robot costs ")?;
+    let (obsidian_ore, rest) = split_once(line, rest, " ore and ")?;
+    let (obsidian_clay, rest) = split_once(line, rest, " clay. Each geode robot costs ")?;
+    let (geode_ore, rest) = split_once(line, rest, " ore and ")?;
+    let (geode_obsidian, _) = split_once(line, rest, " obsidian.")?;
+
+    let ore_ore = number(line, ore_ore)?;
+    let clay_ore = number(line, clay_ore)?;
+    let obsidian_ore = number(line, obsidian_ore)?;
+    let obsidian_clay = number(line, obsidian_clay)?;
+    let geode_ore = number(line, geode_ore)?;
+    let geode_obsidian = number(line, geode_obsidian)?;
+
+    Ok(Blueprint {
+        ore_cost: Vector4::new(ore_ore, 0, 0, 0),
+        clay_cost: Vector4::new(clay_ore, 0, 0, 0),
+        obsidian_cost: Vector4::new(obsidian_ore, obsidian_clay, 0, 0),
+        geode_cost: Vector4::new(geode_ore, 0, geode_obsidian, 0),
+        max_cost: Vector4::new(
+            ore_ore.max(clay_ore).max(obsidian_ore).max(geode_ore),
+            obsidian_clay,
+            geode_obsidian,
+            0,
+        ),
+    })
+}
+
+impl Solver for Day19 {
+    const DAY: u32 = 19;
+
+    type Input = Vec<Blueprint>;
+    type AnswerA = u32;
+    type AnswerB = u32;
+
+    fn parse(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+        parse_lines(input, parse_blueprint)
+    }
+
+    fn part_a(blueprints: &Vec<Blueprint>) -> Option<u32> {
+        let quality = blueprints
+            .iter()
+            .zip(1..)
+            .map(|(b, i)| max_geodes(b, 24) * i)
+            .sum::<u32>();
+
+        Some(quality)
+    }
+
+    fn part_b(blueprints: &Vec<Blueprint>) -> Option<u32> {
+        let product = blueprints
+            .iter()
+            .take(3)
+            .map(|b| max_geodes(b, 32))
+            .product::<u32>();
+
+        Some(product)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::shared::parse::{number, parse_lines};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day20;
+
+pub fn untangle(shuffled: &[usize], numbers: &[i64]) -> Vec<i64> {
+    let mut untangled = vec![0; numbers.len()];
+    numbers
+        .iter()
+        .enumerate()
+        .for_each(|(i, &n)| untangled[shuffled[i]] = n);
+
+    untangled
+}
+
+/// Moves every number by its value, `v` holds the current position of each number.
+pub fn mix(numbers: &[i64], v: &mut [usize]) {
+    for i in 0..v.len() {
+        let value = numbers[i];
+        let pos = v[i] as i64;
+
+        let newpos = (pos + value).rem_euclid(v.len() as i64 - 1);
+
+        match pos.cmp(&newpos) {
+            std::cmp::Ordering::Less => {
+                for shuffled_pos in v.iter_mut() {
+                    if *shuffled_pos > pos as usize && *shuffled_pos <= newpos as usize {
+                        *shuffled_pos -= 1
+                    }
+                }
+            }
+            std::cmp::Ordering::Greater => {
+                for shuffled_pos in v.iter_mut() {
+                    if *shuffled_pos < pos as usize && *shuffled_pos >= newpos as usize {
+                        *shuffled_pos += 1
+                    }
+                }
+            }
+            std::cmp::Ordering::Equal => {}
+        }
+
+        v[i] = newpos as usize;
+    }
+}
+
+fn grove_coordinates(numbers: &[i64], rounds: usize) -> Option<i64> {
+    let l = numbers.len();
+
+    let mut mixed = (0..l).collect::<Vec<usize>>();
+
+    for _ in 0..rounds {
+        mix(numbers, &mut mixed);
+    }
+
+    let untangled = untangle(&mixed, numbers);
+    let zero_pos = untangled.iter().position(|&n| n == 0)?;
+
+    Some(
+        untangled[(zero_pos + 1000) % l]
+            + untangled[(zero_pos + 2000) % l]
+            + untangled[(zero_pos + 3000) % l],
+    )
+}
+
+impl Solver for Day20 {
+    const DAY: u32 = 20;
+
+    type Input = Vec<i64>;
+    type AnswerA = i64;
+    type AnswerB = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        parse_lines(input, |line| number(line, line.trim()))
+    }
+
+    fn part_a(numbers: &Vec<i64>) -> Option<i64> {
+        grove_coordinates(numbers, 1)
+    }
+
+    fn part_b(numbers: &Vec<i64>) -> Option<i64> {
+        let numbers = numbers
+            .iter()
+            .map(|num| num * 811_589_153)
+            .collect::<Vec<i64>>();
+
+        grove_coordinates(&numbers, 10)
+    }
+}
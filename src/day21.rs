@@ -0,0 +1,357 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::shared::parse::{number, parse_lines, split_once, LineError};
+use crate::shared::{ParseError, Solver};
+
+pub struct Day21;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Position {
+    Left,
+    Right,
+}
+
+pub type MonkeyMap = HashMap<String, Expr>;
+pub type InvMap = HashMap<String, (String, Position)>;
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Constant(i64),
+    Add(String, String),
+    Sub(String, String),
+    Mul(String, String),
+    Div(String, String),
+}
+
+fn parse_monkey(line: &str) -> Result<(String, Expr), LineError> {
+    let (name, rhs) = split_once(line, line, ": ")?;
+
+    let expr = match rhs.split_ascii_whitespace().collect::<Vec<&str>>()[..] {
+        [num] => Expr::Constant(number(line, num)?),
+        [left, op, right] => {
+            let (left, right) = (left.to_string(), right.to_string());
+            match op {
+                "+" => Expr::Add(left, right),
+                "-" => Expr::Sub(left, right),
+                "*" => Expr::Mul(left, right),
+                "/" => Expr::Div(left, right),
+                _ => {
+                    return Err(LineError::at(
+                        line,
+                        op,
+                        format!("expected one of + - * /, found '{op}'"),
+                    ))
+                }
+            }
+        }
+        _ => {
+            return Err(LineError::at(
+                line,
+                rhs,
+                "expected a number or '<monkey> <op> <monkey>'",
+            ))
+        }
+    };
+
+    Ok((name.to_string(), expr))
+}
+
+pub fn evaluate(name: &str, m: &MonkeyMap) -> i64 {
+    let expr = m.get(name).expect("Name has entry in map");
+    match expr {
+        Expr::Constant(n) => *n,
+        Expr::Add(n1, n2) => evaluate(n1, m) + evaluate(n2, m),
+        Expr::Sub(n1, n2) => evaluate(n1, m) - evaluate(n2, m),
+        Expr::Mul(n1, n2) => evaluate(n1, m) * evaluate(n2, m),
+        Expr::Div(n1, n2) => evaluate(n1, m) / evaluate(n2, m),
+    }
+}
+
+fn get_operands(e: &Expr) -> Option<(&str, &str)> {
+    match e {
+        Expr::Constant(_) => None,
+        Expr::Add(n1, n2) | Expr::Sub(n1, n2) | Expr::Mul(n1, n2) | Expr::Div(n1, n2) => {
+            Some((n1, n2))
+        }
+    }
+}
+
+/// A monkey that ends up waiting for itself, looking from the monkeys in `names` in order.
+fn waits_for_itself<'a>(m: &'a MonkeyMap, names: &[&'a str]) -> Option<&'a str> {
+    let mut waiting = HashSet::new();
+    let mut done = HashSet::new();
+
+    for &start in names {
+        if done.contains(start) {
+            continue;
+        }
+
+        // The monkeys on the way from start, each with the operand to look at next
+        waiting.insert(start);
+        let mut stack = vec![(start, 0)];
+        while let Some((name, next)) = stack.pop() {
+            let operand = get_operands(&m[name]).and_then(|(n1, n2)| [n1, n2].get(next).copied());
+            let Some(operand) = operand else {
+                waiting.remove(name);
+                done.insert(name);
+                continue;
+            };
+
+            stack.push((name, next + 1));
+            if waiting.contains(operand) {
+                return Some(operand);
+            }
+            if !done.contains(operand) {
+                waiting.insert(operand);
+                stack.push((operand, 0));
+            }
+        }
+    }
+
+    None
+}
+
+/// Whether `root` waits for `humn`, following the monkeys waiting for it.
+pub fn root_waits_for_humn(inv: &InvMap) -> bool {
+    let mut next = "humn";
+    while let Some((lhs, _)) = inv.get(next) {
+        if lhs == "root" {
+            return true;
+        }
+        next = lhs;
+    }
+
+    false
+}
+
+pub fn build_inv_map(m: &MonkeyMap) -> InvMap {
+    let mut inv = InvMap::new();
+    for (name, expr) in m.iter() {
+        if let Some((n1, n2)) = get_operands(expr) {
+            inv.insert(n1.to_string(), (name.clone(), Position::Left));
+            inv.insert(n2.to_string(), (name.clone(), Position::Right));
+        }
+    }
+    inv
+}
+
+fn flip_expression(lhs: String, expr: Expr, pos: Position) -> Expr {
+    match expr {
+        Expr::Add(n1, n2) => match pos {
+            Position::Left => Expr::Sub(lhs, n2),
+            Position::Right => Expr::Sub(lhs, n1),
+        },
+        Expr::Sub(n1, n2) => match pos {
+            Position::Left => Expr::Add(lhs, n2),
+            Position::Right => Expr::Sub(n1, lhs),
+        },
+        Expr::Mul(n1, n2) => match pos {
+            Position::Left => Expr::Div(lhs, n2),
+            Position::Right => Expr::Div(lhs, n1),
+        },
+        Expr::Div(n1, n2) => match pos {
+            Position::Left => Expr::Mul(lhs, n2),
+            Position::Right => Expr::Div(n1, lhs),
+        },
+        Expr::Constant(_) => panic!("Cannot flip constant expression"),
+    }
+}
+
+/// Rewrites the map so that `humn` is expressed in terms of the other monkeys, keeping the
+/// equality required by `root`.
+pub fn express_humn(m: &mut MonkeyMap, inv: &InvMap) {
+    if m.remove("humn").is_none() {
+        panic!("humn not originally present")
+    }
+
+    let mut next = "humn".to_string();
+    loop {
+        let (lhs, pos) = inv.get(&next).expect("next is in inverse map");
+        let expr = m
+            .remove(lhs)
+            .expect("entry to reorder can be found in monkey map");
+        if lhs == "root" {
+            let (left, right) = get_operands(&expr).expect("Expression has operands");
+            match pos {
+                Position::Left => {
+                    m.insert(next, Expr::Constant(evaluate(right, m)));
+                }
+                Position::Right => {
+                    m.insert(next, Expr::Constant(evaluate(left, m)));
+                }
+            }
+            break;
+        }
+
+        let new_expr = flip_expression(lhs.clone(), expr, *pos);
+        if m.insert(next, new_expr).is_some() {
+            panic!("Unexpected")
+        };
+        next = lhs.clone();
+    }
+}
+
+impl Solver for Day21 {
+    const DAY: u32 = 21;
+
+    type Input = MonkeyMap;
+    type AnswerA = i64;
+    type AnswerB = i64;
+
+    fn parse(input: &str) -> Result<MonkeyMap, ParseError> {
+        let monkeys = parse_lines(input, parse_monkey)?;
+
+        let mut lines = HashMap::<&str, usize>::new();
+        for ((name, _), (i, line)) in monkeys.iter().zip(input.lines().enumerate()) {
+            if let Some(first) = lines.insert(name, i) {
+                return Err(ParseError::new(
+                    i + 1,
+                    1,
+                    line,
+                    format!("monkey {name} is already listed on line {}", first + 1),
+                ));
+            }
+        }
+        let m = MonkeyMap::from_iter(monkeys.iter().cloned());
+
+        // Every monkey has to wait only for monkeys that are listed as well
+        for (i, line) in input.lines().enumerate() {
+            let (name, _) = line.split_once(": ").expect("Lines are parsed");
+            if let Some((n1, n2)) = get_operands(&m[name]) {
+                if let Some(unknown) = [n1, n2].into_iter().find(|n| !m.contains_key(*n)) {
+                    let at = line.rfind(unknown).expect("Operand is on the line");
+                    return Err(ParseError::new(
+                        i + 1,
+                        at + 1,
+                        line,
+                        format!("unknown monkey {unknown}"),
+                    ));
+                }
+            }
+        }
+
+        let line_of = |name: &str| {
+            let i = lines[name];
+            (i + 1, input.lines().nth(i).expect("Monkey is on the line"))
+        };
+        match m.get("root") {
+            None => return Err(ParseError::end_of_input(input, "expected the monkey root")),
+            Some(Expr::Constant(_)) => {
+                let (number, line) = line_of("root");
+                return Err(ParseError::new(
+                    number,
+                    1,
+                    line,
+                    "expected root to wait for two monkeys",
+                ));
+            }
+            Some(_) => (),
+        }
+
+        let names = monkeys
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<&str>>();
+        if let Some(name) = waits_for_itself(&m, &names) {
+            let (number, line) = line_of(name);
+            return Err(ParseError::new(
+                number,
+                1,
+                line,
+                format!("monkey {name} waits for itself"),
+            ));
+        }
+
+        Ok(m)
+    }
+
+    fn part_a(m: &MonkeyMap) -> Option<i64> {
+        Some(evaluate("root", m))
+    }
+
+    fn part_b(m: &MonkeyMap) -> Option<i64> {
+        let inv = build_inv_map(m);
+        if !m.contains_key("humn") || !root_waits_for_humn(&inv) {
+            return None;
+        }
+
+        let mut m = m.clone();
+        express_humn(&mut m, &inv);
+        Some(evaluate("humn", &m))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    fn error(input: &str) -> (usize, String) {
+        let e = Day21::parse(input).unwrap_err();
+        (e.line, e.message)
+    }
+
+    #[test]
+    fn example() {
+        let m = Day21::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day21::part_a(&m), Some(152));
+        assert_eq!(Day21::part_b(&m), Some(301));
+    }
+
+    #[test]
+    fn part_a_does_not_need_humn_to_reach_root() {
+        let m = Day21::parse("root: aaaa + bbbb\naaaa: 1\nbbbb: 2\nhumn: 5").unwrap();
+
+        assert_eq!(Day21::part_a(&m), Some(3));
+        assert_eq!(Day21::part_b(&m), None);
+    }
+
+    #[test]
+    fn monkeys_waiting_for_themselves_are_rejected() {
+        assert_eq!(
+            error("a: b + b\nb: a + a\nroot: a + a"),
+            (1, "monkey a waits for itself".to_string())
+        );
+        assert_eq!(
+            error("root: a + c\nc: 1\na: c + a"),
+            (3, "monkey a waits for itself".to_string())
+        );
+        assert_eq!(
+            error("root: a + b\na: b + b\nb: c * c\nc: root - a"),
+            (1, "monkey root waits for itself".to_string())
+        );
+    }
+
+    #[test]
+    fn root_and_names_are_checked() {
+        assert_eq!(
+            error("root: 4\nhumn: 5"),
+            (1, "expected root to wait for two monkeys".to_string())
+        );
+        assert_eq!(
+            error("root: a + a\na: 1\na: 2"),
+            (3, "monkey a is already listed on line 2".to_string())
+        );
+        assert_eq!(
+            error("root: a + b\na: 1"),
+            (1, "unknown monkey b".to_string())
+        );
+    }
+}
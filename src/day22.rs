@@ -0,0 +1,578 @@
+use std::collections::HashMap;
+
+use crate::shared::parse::{number, LineError};
+use crate::shared::{Grid, ParseError, Solver};
+
+pub struct Day22;
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn rotate_cw(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::Right => Direction::Down,
+        }
+    }
+
+    fn rotate_ccw(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    fn eval(self) -> usize {
+        match self {
+            Direction::Up => 3,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction {
+    Move(usize),
+    TurnLeft,
+    TurnRight,
+}
+
+pub type Tile = Grid<char>;
+/// Tiles of the board indexed by `(k, l)`, the tile column and row
+pub type Board = Grid<Option<Tile>>;
+pub type EdgeMap = HashMap<((usize, usize), Direction), (usize, usize)>;
+
+fn board_from_cells(cells: &Grid<char>, a: usize) -> Result<Board, ParseError> {
+    let mut board: Board = Grid::new(cells.width() / a, cells.height() / a, None);
+
+    for (kk, ll) in board.positions().collect::<Vec<(usize, usize)>>() {
+        if cells[(kk * a, ll * a)] == ' ' {
+            continue;
+        }
+
+        let tile = cells
+            .sub_grid((kk * a, ll * a), a, a)
+            .expect("Tiles are within the board");
+
+        // Every row of the tile has to be filled with open tiles and walls
+        if let Some(((x, y), _)) = tile.iter().find(|&(_, &c)| c == ' ') {
+            let (x, y) = (kk * a + x, ll * a + y);
+            return Err(ParseError::new(
+                y + 1,
+                x + 1,
+                cells.row(y).iter().collect::<String>().trim_end(),
+                format!("expected the tile of size {a} to continue"),
+            ));
+        }
+
+        board[(kk, ll)] = Some(tile);
+    }
+
+    // Walking off the board wraps around along the row or column of tiles, which has to be one
+    // run of tiles
+    let text = |y: usize| {
+        cells
+            .row(y)
+            .iter()
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    };
+    for ll in 0..board.height() {
+        match check_run(board.row(ll).iter().map(Option::is_some)) {
+            Ok(()) => (),
+            Err(None) => {
+                return Err(ParseError::new(
+                    ll * a + 1,
+                    1,
+                    &text(ll * a),
+                    format!("expected a tile of size {a} in this row"),
+                ))
+            }
+            Err(Some(kk)) => {
+                return Err(ParseError::new(
+                    ll * a + 1,
+                    kk * a + 1,
+                    &text(ll * a),
+                    "expected the tiles of this row to be next to each other",
+                ))
+            }
+        }
+    }
+    for kk in 0..board.width() {
+        match check_run(board.column(kk).map(Option::is_some)) {
+            Ok(()) => (),
+            Err(None) => {
+                return Err(ParseError::new(
+                    1,
+                    kk * a + 1,
+                    &text(0),
+                    format!("expected a tile of size {a} in this column"),
+                ))
+            }
+            Err(Some(ll)) => {
+                return Err(ParseError::new(
+                    ll * a + 1,
+                    kk * a + 1,
+                    &text(ll * a),
+                    "expected the tiles of this column to be next to each other",
+                ))
+            }
+        }
+    }
+
+    Ok(board)
+}
+
+/// Checks that the tiles along a row or column of tiles are next to each other. Fails with `None`
+/// when there is no tile at all, or with the tile starting a second run.
+fn check_run(present: impl Iterator<Item = bool>) -> Result<(), Option<usize>> {
+    let present = present.collect::<Vec<bool>>();
+    let first = present.iter().position(|&p| p).ok_or(None)?;
+    let end = present[first..]
+        .iter()
+        .position(|&p| !p)
+        .map_or(present.len(), |n| first + n);
+
+    match present[end..].iter().position(|&p| p) {
+        Some(n) => Err(Some(end + n)),
+        None => Ok(()),
+    }
+}
+
+/// Where walking off each tile wraps around to. Panics unless every row and column of the board
+/// is one run of tiles, which parsing checks.
+pub fn map_edges(board: &Board) -> EdgeMap {
+    let mut map = EdgeMap::new();
+    let (k, l) = (board.width(), board.height());
+
+    for ll in 0..l {
+        let first = board
+            .row(ll)
+            .iter()
+            .position(|maybe_tile| maybe_tile.is_some())
+            .expect("Some tile at least once in row");
+
+        let mut last = k - 1;
+        for kk in (first + 1)..k {
+            if board[(kk, ll)].is_some() {
+                if map
+                    .insert(((kk - 1, ll), Direction::Right), (kk, ll))
+                    .is_some()
+                {
+                    panic!("Unexpected")
+                }
+
+                if map
+                    .insert(((kk, ll), Direction::Left), (kk - 1, ll))
+                    .is_some()
+                {
+                    panic!("Unexpected")
+                }
+            } else {
+                last = kk - 1;
+                break;
+            }
+        }
+
+        if map
+            .insert(((last, ll), Direction::Right), (first, ll))
+            .is_some()
+        {
+            panic!("Unexpected")
+        }
+
+        if map
+            .insert(((first, ll), Direction::Left), (last, ll))
+            .is_some()
+        {
+            panic!("Unexpected")
+        }
+    }
+
+    for kk in 0..k {
+        let first = board
+            .column(kk)
+            .position(|maybe_tile| maybe_tile.is_some())
+            .expect("Some tile at least once in column");
+
+        let mut last = l - 1;
+
+        for ll in (first + 1)..l {
+            if board[(kk, ll)].is_some() {
+                if map
+                    .insert(((kk, ll - 1), Direction::Down), (kk, ll))
+                    .is_some()
+                {
+                    panic!("Unexpected")
+                }
+
+                if map
+                    .insert(((kk, ll), Direction::Up), (kk, ll - 1))
+                    .is_some()
+                {
+                    panic!("Unexpected")
+                }
+            } else {
+                last = ll - 1;
+                break;
+            }
+        }
+
+        if map
+            .insert(((kk, last), Direction::Down), (kk, first))
+            .is_some()
+        {
+            panic!("Unexpected")
+        }
+
+        if map
+            .insert(((kk, first), Direction::Up), (kk, last))
+            .is_some()
+        {
+            panic!("Unexpected")
+        }
+    }
+
+    map
+}
+
+fn process_instructions(s: &str) -> Result<Vec<Instruction>, LineError> {
+    let mut instructions = Vec::<Instruction>::new();
+    let mut rest = s;
+
+    while let Some(c) = rest.chars().next() {
+        match c {
+            'L' => {
+                instructions.push(Instruction::TurnLeft);
+                rest = &rest[1..];
+            }
+            'R' => {
+                instructions.push(Instruction::TurnRight);
+                rest = &rest[1..];
+            }
+            d if d.is_ascii_digit() => {
+                let end = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                instructions.push(Instruction::Move(number(s, &rest[..end])?));
+                rest = &rest[end..];
+            }
+            _ => {
+                return Err(LineError::at(
+                    s,
+                    rest,
+                    format!("expected a number, 'L' or 'R', found '{c}'"),
+                ))
+            }
+        }
+    }
+
+    Ok(instructions)
+}
+
+/// The leftmost open tile of the top row as tile column, tile row and position in the tile.
+fn locate_start(board: &Board) -> Option<(usize, usize, usize, usize)> {
+    board.iter().find_map(|((kk, ll), maybe_tile)| {
+        let tile = maybe_tile.as_ref()?;
+        let ((x, y), _) = tile.iter().find(|&(_, &c)| c == '.')?;
+        Some((kk, ll, x, y))
+    })
+}
+
+/// The board cut into square tiles of side `a`, and the path to follow.
+pub struct Notes {
+    pub board: Board,
+    pub a: usize,
+    pub instructions: Vec<Instruction>,
+}
+
+fn parse_notes(input: &str) -> Result<Notes, ParseError> {
+    let mut lines = input.lines().enumerate();
+    let board_lines = lines
+        .by_ref()
+        .take_while(|(_, line)| !line.is_empty())
+        .map(
+            |(i, line)| match line.find(|c| !matches!(c, ' ' | '.' | '#')) {
+                Some(x) => Err(ParseError::new(
+                    i + 1,
+                    x + 1,
+                    line,
+                    "expected ' ', '.' or '#'",
+                )),
+                None => Ok(line.chars().collect::<Vec<char>>()),
+            },
+        )
+        .collect::<Result<Vec<Vec<char>>, ParseError>>()?;
+
+    if board_lines.is_empty() {
+        return Err(ParseError::new(1, 1, "", "expected the board"));
+    }
+
+    let (i, ins_str) = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, "expected the path"))?;
+    let instructions = process_instructions(ins_str).map_err(|e| e.on_line(i + 1, ins_str))?;
+
+    // The maximum line length is also the width of the board, shorter lines are padded
+    let width = board_lines.iter().map(|line| line.len()).max().unwrap_or(0);
+    let cells = Grid::from_rows(
+        board_lines
+            .into_iter()
+            .map(|mut line| {
+                line.resize(width, ' ');
+                line
+            })
+            .collect(),
+    )
+    .expect("Lines are padded to the same length");
+
+    // The tile size
+    let a = gcd::binary_usize(cells.width(), cells.height());
+
+    Ok(Notes {
+        board: board_from_cells(&cells, a)?,
+        a,
+        instructions,
+    })
+}
+
+fn follow_path(notes: &Notes) -> Option<usize> {
+    let Notes {
+        board,
+        a,
+        instructions,
+    } = notes;
+    let a = *a;
+
+    let edge_map = map_edges(board);
+
+    let (mut pos_k, mut pos_l, mut pos_x, mut pos_y) = locate_start(board)?;
+    let mut dir = Direction::Right;
+
+    for instruction in instructions {
+        match *instruction {
+            Instruction::Move(mut n) => {
+                let mut this_tile = board[(pos_k, pos_l)]
+                    .as_ref()
+                    .expect("Motion is consistent");
+
+                match dir {
+                    Direction::Up => {
+                        while n > 0 {
+                            if pos_y == 0 {
+                                let &(new_k, new_l) = edge_map
+                                    .get(&((pos_k, pos_l), dir))
+                                    .expect("Edges are mapped");
+                                let new_tile = board[(new_k, new_l)]
+                                    .as_ref()
+                                    .expect("Edges are mapped correctly");
+
+                                if new_tile[(pos_x, a - 1)] == '#' {
+                                    break;
+                                } else {
+                                    this_tile = new_tile;
+                                    pos_k = new_k;
+                                    pos_l = new_l;
+                                    pos_y = a - 1;
+                                }
+                            } else if this_tile[(pos_x, pos_y - 1)] == '#' {
+                                break;
+                            } else {
+                                pos_y -= 1;
+                            }
+
+                            n -= 1;
+                        }
+                    }
+                    Direction::Down => {
+                        while n > 0 {
+                            if pos_y == a - 1 {
+                                let &(new_k, new_l) = edge_map
+                                    .get(&((pos_k, pos_l), dir))
+                                    .expect("Edges are mapped");
+                                let new_tile = board[(new_k, new_l)]
+                                    .as_ref()
+                                    .expect("Edges are mapped correctly");
+
+                                if new_tile[(pos_x, 0)] == '#' {
+                                    break;
+                                } else {
+                                    this_tile = new_tile;
+                                    pos_k = new_k;
+                                    pos_l = new_l;
+                                    pos_y = 0;
+                                }
+                            } else if this_tile[(pos_x, pos_y + 1)] == '#' {
+                                break;
+                            } else {
+                                pos_y += 1;
+                            }
+
+                            n -= 1;
+                        }
+                    }
+                    Direction::Left => {
+                        while n > 0 {
+                            if pos_x == 0 {
+                                let &(new_k, new_l) = edge_map
+                                    .get(&((pos_k, pos_l), dir))
+                                    .expect("Edges are mapped");
+                                let new_tile = board[(new_k, new_l)]
+                                    .as_ref()
+                                    .expect("Edges are mapped correctly");
+
+                                if new_tile[(a - 1, pos_y)] == '#' {
+                                    break;
+                                } else {
+                                    this_tile = new_tile;
+                                    pos_k = new_k;
+                                    pos_l = new_l;
+                                    pos_x = a - 1;
+                                }
+                            } else if this_tile[(pos_x - 1, pos_y)] == '#' {
+                                break;
+                            } else {
+                                pos_x -= 1;
+                            }
+
+                            n -= 1;
+                        }
+                    }
+                    Direction::Right => {
+                        while n > 0 {
+                            if pos_x == a - 1 {
+                                let &(new_k, new_l) = edge_map
+                                    .get(&((pos_k, pos_l), dir))
+                                    .expect("Edges are mapped");
+                                let new_tile = board[(new_k, new_l)]
+                                    .as_ref()
+                                    .expect("Edges are mapped correctly");
+
+                                if new_tile[(0, pos_y)] == '#' {
+                                    break;
+                                } else {
+                                    this_tile = new_tile;
+                                    pos_k = new_k;
+                                    pos_l = new_l;
+                                    pos_x = 0;
+                                }
+                            } else if this_tile[(pos_x + 1, pos_y)] == '#' {
+                                break;
+                            } else {
+                                pos_x += 1;
+                            }
+
+                            n -= 1;
+                        }
+                    }
+                }
+            }
+            Instruction::TurnLeft => dir = dir.rotate_ccw(),
+            Instruction::TurnRight => dir = dir.rotate_cw(),
+        }
+    }
+
+    Some(1000 * (pos_l * a + pos_y + 1) + 4 * (pos_k * a + pos_x + 1) + dir.eval())
+}
+
+impl Solver for Day22 {
+    const DAY: u32 = 22;
+
+    type Input = Notes;
+    type AnswerA = usize;
+    type AnswerB = usize;
+
+    fn parse(input: &str) -> Result<Notes, ParseError> {
+        parse_notes(input)
+    }
+
+    fn part_a(notes: &Notes) -> Option<usize> {
+        follow_path(notes)
+    }
+
+    fn part_b(_notes: &Notes) -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+    fn error(input: &str) -> (usize, usize, String) {
+        let e = Day22::parse(input).err().expect("Input is rejected");
+        (e.line, e.column, e.message)
+    }
+
+    #[test]
+    fn example() {
+        let notes = Day22::parse(EXAMPLE).unwrap();
+
+        assert_eq!(notes.a, 4);
+        assert_eq!(Day22::part_a(&notes), Some(6032));
+    }
+
+    #[test]
+    fn rows_and_columns_need_one_run_of_tiles() {
+        assert_eq!(
+            error("  .\n\n1"),
+            (1, 1, "expected a tile of size 1 in this column".to_string())
+        );
+        assert_eq!(
+            error("   \n...\n\n1"),
+            (1, 1, "expected a tile of size 1 in this row".to_string())
+        );
+        assert_eq!(
+            error(". .\n...\n\n1"),
+            (
+                1,
+                3,
+                "expected the tiles of this row to be next to each other".to_string()
+            )
+        );
+        assert_eq!(
+            error("..\n .\n..\n\n1"),
+            (
+                3,
+                1,
+                "expected the tiles of this column to be next to each other".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn walking_wraps_around_rows_and_columns() {
+        let notes = Day22::parse(" ..\n...\n\n4R1").unwrap();
+
+        // Right from (1, 0) wraps to (1, 0) after 4 steps, then one step down
+        assert_eq!(Day22::part_a(&notes), Some(2000 + 8 + 1));
+    }
+}
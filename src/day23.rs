@@ -0,0 +1,24 @@
+use crate::shared::{ParseError, Solver};
+
+/// Not solved yet: the input is kept as its lines and both parts have no answer.
+pub struct Day23;
+
+impl Solver for Day23 {
+    const DAY: u32 = 23;
+
+    type Input = Vec<String>;
+    type AnswerA = u64;
+    type AnswerB = u64;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part_a(_lines: &Vec<String>) -> Option<u64> {
+        None
+    }
+
+    fn part_b(_lines: &Vec<String>) -> Option<u64> {
+        None
+    }
+}
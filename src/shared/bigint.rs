@@ -0,0 +1,333 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+/// Unsigned integer of any size, stored as base 2^32 digits with the least significant first and
+/// without leading zero digits, so zero has no digits at all.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    digits: Vec<u32>,
+}
+
+impl BigUint {
+    fn trimmed(mut digits: Vec<u32>) -> BigUint {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        BigUint { digits }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Quotient and remainder of division by `divisor`, `None` when it is zero.
+    pub fn div_rem_u64(&self, divisor: u64) -> Option<(BigUint, u64)> {
+        if divisor == 0 {
+            return None;
+        }
+
+        let mut quotient = vec![0u32; self.digits.len()];
+        let mut remainder = 0u128;
+        for (i, &digit) in self.digits.iter().enumerate().rev() {
+            let current = (remainder << 32) | digit as u128;
+            quotient[i] = (current / divisor as u128) as u32;
+            remainder = current % divisor as u128;
+        }
+
+        Some((BigUint::trimmed(quotient), remainder as u64))
+    }
+
+    /// `self - other`, `None` when it would be negative.
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if *self < *other {
+            return None;
+        }
+
+        let mut digits = Vec::with_capacity(self.digits.len());
+        let mut borrow = 0i64;
+        for (i, &digit) in self.digits.iter().enumerate() {
+            let mut difference = digit as i64 - *other.digits.get(i).unwrap_or(&0) as i64 - borrow;
+            borrow = 0;
+            if difference < 0 {
+                difference += 1 << 32;
+                borrow = 1;
+            }
+            digits.push(difference as u32);
+        }
+
+        Some(BigUint::trimmed(digits))
+    }
+
+    /// Quotient and remainder of division by `divisor`, `None` when it is zero. Long division
+    /// one bit at a time.
+    pub fn div_rem(&self, divisor: &BigUint) -> Option<(BigUint, BigUint)> {
+        if divisor.is_zero() {
+            return None;
+        }
+        if let Some(small) = divisor.to_u64() {
+            let (quotient, remainder) = self.div_rem_u64(small)?;
+            return Some((quotient, BigUint::from(remainder)));
+        }
+
+        let mut quotient = vec![0u32; self.digits.len()];
+        let mut remainder = BigUint::default();
+        for bit in (0..self.digits.len() * 32).rev() {
+            remainder = &remainder + &remainder;
+            if self.digits[bit / 32] >> (bit % 32) & 1 == 1 {
+                remainder = &remainder + &BigUint::from(1);
+            }
+            if remainder >= *divisor {
+                remainder = remainder
+                    .checked_sub(divisor)
+                    .expect("Remainder is at least the divisor");
+                quotient[bit / 32] |= 1 << (bit % 32);
+            }
+        }
+
+        Some((BigUint::trimmed(quotient), remainder))
+    }
+
+    /// The value if it fits into a `u64`.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.digits[..] {
+            [] => Some(0),
+            [low] => Some(low as u64),
+            [low, high] => Some((high as u64) << 32 | low as u64),
+            _ => None,
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> BigUint {
+        BigUint::trimmed(vec![value as u32, (value >> 32) as u32])
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let len = self.digits.len().max(other.digits.len());
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+        for i in 0..len {
+            let sum = carry
+                + *self.digits.get(i).unwrap_or(&0) as u64
+                + *other.digits.get(i).unwrap_or(&0) as u64;
+            digits.push(sum as u32);
+            carry = sum >> 32;
+        }
+        digits.push(carry as u32);
+
+        BigUint::trimmed(digits)
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut digits = vec![0u32; self.digits.len() + other.digits.len()];
+        for (i, &a) in self.digits.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.digits.iter().enumerate() {
+                let product = a as u64 * b as u64 + digits[i + j] as u64 + carry;
+                digits[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            digits[i + other.digits.len()] = carry as u32;
+        }
+
+        BigUint::trimmed(digits)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.digits
+            .len()
+            .cmp(&other.digits.len())
+            .then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u64 = 1_000_000_000;
+
+        // Groups of 9 decimal digits, the least significant first
+        let mut chunks = Vec::<u64>::new();
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (quotient, chunk) = rest.div_rem_u64(CHUNK).expect("Divisor is not zero");
+            chunks.push(chunk);
+            rest = quotient;
+        }
+
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((first, others)) => {
+                write!(f, "{first}")?;
+                for chunk in others.iter().rev() {
+                    write!(f, "{chunk:09}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(value: u128) -> BigUint {
+        let shift = BigUint::from(1 << 32);
+        let high = &(&BigUint::from((value >> 64) as u64) * &shift) * &shift;
+        &high + &BigUint::from(value as u64)
+    }
+
+    fn to_u128(value: &BigUint) -> u128 {
+        value.to_string().parse().unwrap()
+    }
+
+    /// Values around the limb boundaries, where carries and borrows happen.
+    fn edges() -> Vec<u128> {
+        let mut values = vec![0, 1, 2, 3, 10, 1_000_000_000, 999_999_999_999_999_999];
+        for bits in [31, 32, 33, 63, 64, 65, 95, 96, 97, 126, 127] {
+            values.extend([(1 << bits) - 1, 1 << bits, (1 << bits) + 1]);
+        }
+        values.push(u128::MAX);
+        values
+    }
+
+    /// A few thousand values of every size, from a small xorshift generator.
+    fn randoms() -> Vec<u128> {
+        let mut state = 0x853c_49e6_748f_ea9b_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        (0..2000)
+            .map(|_| {
+                let value = (next() as u128) << 64 | next() as u128;
+                value >> (next() % 128)
+            })
+            .collect()
+    }
+
+    fn values() -> Vec<u128> {
+        edges().into_iter().chain(randoms()).collect()
+    }
+
+    #[test]
+    fn display_and_conversions_agree_with_u128() {
+        assert_eq!(BigUint::default().to_string(), "0");
+        assert!(BigUint::from(0).is_zero());
+        for value in values() {
+            assert_eq!(big(value).to_string(), value.to_string());
+            assert_eq!(big(value).to_u64(), u64::try_from(value).ok());
+            assert_eq!(big(value).is_zero(), value == 0);
+        }
+    }
+
+    #[test]
+    fn add_agrees_with_u128() {
+        let values = values();
+        for (&a, &b) in values.iter().zip(values.iter().rev()) {
+            if let Some(sum) = a.checked_add(b) {
+                assert_eq!(to_u128(&(&big(a) + &big(b))), sum, "{a} + {b}");
+            }
+        }
+        let carried = &big(u128::MAX) + &BigUint::from(1);
+        assert_eq!(
+            carried.to_string(),
+            "340282366920938463463374607431768211456"
+        );
+    }
+
+    #[test]
+    fn mul_agrees_with_u128() {
+        let values = values();
+        for (&a, &b) in values.iter().zip(values.iter().skip(1)) {
+            let (a, b) = (a >> 64, b % (1 << 64));
+            assert_eq!(to_u128(&(&big(a) * &big(b))), a * b, "{a} * {b}");
+        }
+        assert_eq!(
+            (&big(u64::MAX as u128) * &big(u64::MAX as u128)).to_string(),
+            (u64::MAX as u128 * u64::MAX as u128).to_string()
+        );
+        assert!((&big(12345) * &BigUint::default()).is_zero());
+    }
+
+    #[test]
+    fn checked_sub_agrees_with_u128() {
+        let values = values();
+        for (&a, &b) in values.iter().zip(values.iter().rev()) {
+            let difference = big(a).checked_sub(&big(b));
+            assert_eq!(
+                difference.as_ref().map(to_u128),
+                a.checked_sub(b),
+                "{a} - {b}"
+            );
+        }
+    }
+
+    #[test]
+    fn ordering_agrees_with_u128() {
+        let values = values();
+        for (&a, &b) in values.iter().zip(values.iter().skip(3)) {
+            assert_eq!(big(a).cmp(&big(b)), a.cmp(&b), "{a} <=> {b}");
+        }
+    }
+
+    #[test]
+    fn div_rem_u64_agrees_with_u128() {
+        assert_eq!(big(5).div_rem_u64(0), None);
+        let values = values();
+        for (&a, &b) in values.iter().zip(values.iter().skip(5)) {
+            let divisor = (b as u64).max(1);
+            let (quotient, remainder) = big(a).div_rem_u64(divisor).unwrap();
+            assert_eq!(to_u128(&quotient), a / divisor as u128, "{a} / {divisor}");
+            assert_eq!(remainder as u128, a % divisor as u128, "{a} % {divisor}");
+        }
+    }
+
+    #[test]
+    fn div_rem_agrees_with_u128() {
+        assert_eq!(big(5).div_rem(&BigUint::default()), None);
+        let values = values();
+        for (&a, &b) in values.iter().zip(values.iter().skip(7)) {
+            // Divisors of several limbs take the long division rather than div_rem_u64
+            let divisor = b.max(1);
+            let (quotient, remainder) = big(a).div_rem(&big(divisor)).unwrap();
+            assert_eq!(to_u128(&quotient), a / divisor, "{a} / {divisor}");
+            assert_eq!(to_u128(&remainder), a % divisor, "{a} % {divisor}");
+        }
+    }
+
+    #[test]
+    fn division_undoes_multiplication_past_u128() {
+        let values = values();
+        for (&a, &b) in values.iter().zip(values.iter().skip(11)) {
+            let (a, b) = (big(a), big(b.max(1)));
+            let product = &a * &b;
+            let (quotient, remainder) = (&product + &BigUint::from(7)).div_rem(&b).unwrap();
+            let expected = BigUint::from(7).div_rem(&b).unwrap();
+            assert_eq!(quotient, &a + &expected.0);
+            assert_eq!(remainder, expected.1);
+        }
+    }
+}
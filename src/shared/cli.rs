@@ -0,0 +1,98 @@
+use std::error::Error;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+use super::runner::Part;
+
+/// Reads the puzzle input from the first stream and writes what the options ask for to the
+/// second.
+pub type Run = fn(Part, &Options, &mut dyn BufRead, &mut dyn Write) -> Result<(), Box<dyn Error>>;
+
+/// What the binaries of a day do when given options, the same for both parts. Without options
+/// they only solve their part.
+pub struct Cli {
+    pub flags: &'static [&'static str],
+    pub valued: &'static [&'static str],
+    pub run: Run,
+}
+
+impl Cli {
+    /// Does what a binary started with `args` does on `input`, returns what it writes.
+    pub fn run_on(&self, part: Part, args: &[&str], input: &str) -> Result<String, Box<dyn Error>> {
+        let args = args
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<String>>();
+        let options = Options::parse(&args, self.flags, self.valued)?;
+        let mut output = Vec::new();
+        (self.run)(part, &options, &mut input.as_bytes(), &mut output)?;
+
+        Ok(String::from_utf8(output)?)
+    }
+}
+
+/// Options of a per-day binary: `--name` switches and `--name value` pairs.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    flags: Vec<String>,
+    values: Vec<(String, String)>,
+}
+
+impl Options {
+    /// Accepts only the listed `flags` and options taking a value, in any order.
+    pub fn parse(args: &[String], flags: &[&str], valued: &[&str]) -> Result<Options, String> {
+        let mut options = Options::default();
+        let mut rest = args.iter();
+
+        while let Some(arg) = rest.next() {
+            if flags.contains(&arg.as_str()) {
+                options.flags.push(arg.clone());
+            } else if valued.contains(&arg.as_str()) {
+                let value = rest.next().ok_or(format!("Missing value for {arg}"))?;
+                options.values.push((arg.clone(), value.clone()));
+            } else {
+                let mut known = flags.iter().chain(valued).copied().collect::<Vec<&str>>();
+                known.sort_unstable();
+                return Err(format!(
+                    "Unexpected argument '{arg}', known options are: {}",
+                    known.join(", ")
+                ));
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Parses the arguments the binary was started with.
+    pub fn from_env(flags: &[&str], valued: &[&str]) -> Result<Options, String> {
+        let args = std::env::args().skip(1).collect::<Vec<String>>();
+        Options::parse(&args, flags, valued)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.flags.is_empty() && self.values.is_empty()
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+
+    /// The value given last for `name`.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(option, _)| option == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn parsed<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.value(name)
+            .map(|value| {
+                value
+                    .parse::<T>()
+                    .map_err(|_| format!("Invalid value '{value}' for {name}"))
+            })
+            .transpose()
+    }
+}
@@ -0,0 +1,427 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use super::ParseError;
+
+/// A position in a grid as `(x, y)`, `x` grows to the right and `y` grows downwards.
+pub type Position = (usize, usize);
+
+/// Rectangular grid of cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Returns `None` when the rows are not all of the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a map with one character per cell, `cell` returns `None` for characters that are
+    /// not `expected`.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut width = None;
+        let mut cells = Vec::<T>::new();
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let before = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        return Err(ParseError::new(
+                            y + 1,
+                            x + 1,
+                            line,
+                            format!("expected {expected}, found '{c}'"),
+                        ))
+                    }
+                }
+            }
+
+            let row_width = cells.len() - before;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(ParseError::new(
+                        y + 1,
+                        row_width.min(width) + 1,
+                        line,
+                        format!("expected a row of {width} cells"),
+                    ))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, p: Position) -> Option<&T> {
+        if self.contains(p) {
+            Some(&self.cells[p.1 * self.width + p.0])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Position) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.1 * self.width + p.0])
+        } else {
+            None
+        }
+    }
+
+    /// Moves from `p` by `(dx, dy)`, provided the result stays within the grid.
+    pub fn offset(&self, (x, y): Position, (dx, dy): (isize, isize)) -> Option<Position> {
+        let p = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        if self.contains(p) {
+            Some(p)
+        } else {
+            None
+        }
+    }
+
+    /// Positions up, right, down and left of `p` that are within the grid.
+    pub fn neighbours4(&self, p: Position) -> impl Iterator<Item = Position> + '_ {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |d| self.offset(p, d))
+    }
+
+    /// Positions around `p` including the diagonal ones that are within the grid.
+    pub fn neighbours8(&self, p: Position) -> impl Iterator<Item = Position> + '_ {
+        [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ]
+        .into_iter()
+        .filter_map(move |d| self.offset(p, d))
+    }
+
+    /// Positions visited when stepping from `p` by `(dx, dy)` until the edge of the grid, `p`
+    /// itself is not included.
+    pub fn ray(&self, p: Position, d: (isize, isize)) -> impl Iterator<Item = Position> + '_ {
+        std::iter::successors(self.offset(p, d), move |&p| self.offset(p, d))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Panics when `x` is not within the grid, like `row` does for `y`.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column is within the grid");
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The part of the grid of the given size with its top left corner at `p`.
+    pub fn sub_grid(&self, (x, y): Position, width: usize, height: usize) -> Option<Grid<T>>
+    where
+        T: Clone,
+    {
+        if x + width > self.width || y + height > self.height {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height,
+            cells: (y..y + height)
+                .flat_map(|yy| self.row(yy)[x..x + width].iter().cloned())
+                .collect(),
+        })
+    }
+
+    /// Draws the grid with one character per cell.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut cell).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Position) -> &T {
+        self.get(p).expect("Position is within the grid")
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, p: Position) -> &mut T {
+        self.get_mut(p).expect("Position is within the grid")
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456", "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parse_reads_rows_of_cells() {
+        let grid = digits();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(0), &[1, 2, 3]);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+    }
+
+    #[test]
+    fn parse_reports_unexpected_characters_and_ragged_rows() {
+        let e = Grid::parse("12\n3x", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+
+        let e = Grid::parse("123\n45", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+    }
+
+    #[test]
+    fn empty_input_is_an_empty_grid() {
+        let grid = Grid::parse("", "a digit", |c| c.to_digit(10)).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.positions().count(), 0);
+    }
+
+    #[test]
+    fn from_rows_requires_equal_rows() {
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]),
+            Some(digits())
+        );
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+    }
+
+    #[test]
+    fn get_and_index_stay_within_the_grid() {
+        let mut grid = digits();
+
+        assert_eq!(grid.get((2, 1)), Some(&6));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid[(1, 0)], 2);
+
+        grid[(1, 0)] = 7;
+        *grid.get_mut((0, 1)).unwrap() = 8;
+        assert_eq!(grid.row(0), &[1, 7, 3]);
+        assert_eq!(grid.row(1), &[8, 5, 6]);
+        assert_eq!(grid.get_mut((5, 5)), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_outside_the_grid_panics() {
+        let _ = digits()[(3, 0)];
+    }
+
+    #[test]
+    fn offset_stops_at_the_edges() {
+        let grid = digits();
+
+        assert_eq!(grid.offset((1, 0), (1, 1)), Some((2, 1)));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (0, -1)), None);
+        assert_eq!(grid.offset((2, 1), (1, 0)), None);
+        assert_eq!(grid.offset((2, 1), (0, 1)), None);
+    }
+
+    #[test]
+    fn neighbours_are_within_the_grid() {
+        let grid = digits();
+
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<Position>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<Position>>(),
+            [(1, 0), (2, 1), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours8((1, 1)).collect::<Vec<Position>>(),
+            [(1, 0), (2, 0), (2, 1), (0, 1), (0, 0)]
+        );
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+    }
+
+    #[test]
+    fn ray_runs_to_the_edge_without_its_start() {
+        let grid = Grid::new(4, 4, 0);
+
+        assert_eq!(
+            grid.ray((0, 0), (1, 1)).collect::<Vec<Position>>(),
+            [(1, 1), (2, 2), (3, 3)]
+        );
+        assert_eq!(
+            grid.ray((2, 1), (0, -1)).collect::<Vec<Position>>(),
+            [(2, 0)]
+        );
+        assert_eq!(grid.ray((3, 2), (1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits();
+
+        assert_eq!(grid.rows().collect::<Vec<&[u32]>>(), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<u32>>(), [2, 5]);
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.copied().collect::<Vec<u32>>())
+                .collect::<Vec<Vec<u32>>>(),
+            [[1, 4], [2, 5], [3, 6]]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn column_outside_the_grid_panics() {
+        let _ = digits().column(4);
+    }
+
+    #[test]
+    fn positions_and_iter_go_row_by_row() {
+        let grid = digits();
+
+        assert_eq!(
+            grid.positions().collect::<Vec<Position>>(),
+            [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+        assert_eq!(
+            grid.iter().map(|(p, &cell)| (p, cell)).last(),
+            Some(((2, 1), 6))
+        );
+    }
+
+    #[test]
+    fn map_keeps_the_shape() {
+        let doubled = digits().map(|cell| cell * 2);
+
+        assert_eq!(doubled.row(1), &[8, 10, 12]);
+        assert_eq!((doubled.width(), doubled.height()), (3, 2));
+    }
+
+    #[test]
+    fn sub_grid_must_fit() {
+        let grid = digits();
+
+        assert_eq!(
+            grid.sub_grid((1, 0), 2, 2),
+            Grid::from_rows(vec![vec![2, 3], vec![5, 6]])
+        );
+        assert_eq!(grid.sub_grid((2, 0), 2, 1), None);
+        assert_eq!(grid.sub_grid((0, 1), 1, 2), None);
+    }
+
+    #[test]
+    fn display_and_render_draw_rows() {
+        let grid = digits();
+
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(
+            grid.render(|&cell| if cell % 2 == 0 { '#' } else { '.' }),
+            ".#.\n#.#"
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use super::Grid;
+
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// The grid as a binary PPM image, every cell becomes a square of `scale` by `scale` pixels.
+pub fn ppm(colors: &Grid<Rgb>, scale: usize) -> Vec<u8> {
+    let mut image = format!(
+        "P6\n{} {}\n255\n",
+        colors.width() * scale,
+        colors.height() * scale
+    )
+    .into_bytes();
+    for row in colors.rows() {
+        for _ in 0..scale {
+            for color in row {
+                for _ in 0..scale {
+                    image.extend_from_slice(color);
+                }
+            }
+        }
+    }
+
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells_become_scaled_squares() {
+        let (red, blue) = ([255, 0, 0], [0, 0, 255]);
+        let colors = Grid::from_rows(vec![vec![red, blue]]).unwrap();
+
+        let mut expected = b"P6\n4 2\n255\n".to_vec();
+        for _ in 0..2 {
+            expected.extend([red, red, blue, blue].concat());
+        }
+        assert_eq!(ppm(&colors, 2), expected);
+    }
+
+    #[test]
+    fn empty_grid_is_only_a_header() {
+        assert_eq!(ppm(&Grid::new(0, 0, [0, 0, 0]), 3), b"P6\n0 0\n255\n");
+    }
+}
@@ -0,0 +1,48 @@
+use std::error::Error;
+use std::fmt;
+
+pub mod bigint;
+pub mod cli;
+pub mod grid;
+pub mod image;
+pub mod parse;
+#[cfg(test)]
+pub(crate) mod random;
+pub mod runner;
+pub mod search;
+
+pub use bigint::BigUint;
+pub use grid::Grid;
+pub use parse::ParseError;
+
+/// Reads the whole standard input, which is where the per-day binaries take their puzzle input from.
+pub fn read_stdin() -> String {
+    std::io::read_to_string(std::io::stdin()).expect("Standard input is read")
+}
+
+/// A day's puzzle: the input is parsed once and then shared by both parts.
+///
+/// The parts return `None` when the input has no answer or when the part is not solved yet.
+pub trait Solver {
+    /// The day of December the puzzle was published on.
+    const DAY: u32;
+
+    type Input;
+    type AnswerA: fmt::Display;
+    type AnswerB: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_a(input: &Self::Input) -> Option<Self::AnswerA>;
+    fn part_b(input: &Self::Input) -> Option<Self::AnswerB>;
+
+    /// Like `part_a`, but can tell why solving failed. The runner calls this, so parts that can
+    /// fail in a way worth reporting override it.
+    fn try_part_a(input: &Self::Input) -> Result<Option<Self::AnswerA>, Box<dyn Error>> {
+        Ok(Self::part_a(input))
+    }
+
+    /// Like `part_b`, see `try_part_a`.
+    fn try_part_b(input: &Self::Input) -> Result<Option<Self::AnswerB>, Box<dyn Error>> {
+        Ok(Self::part_b(input))
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Where and why an input could not be parsed. Lines and columns are counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Filled in by the runner, the parsers themselves do not know which day they belong to.
+    pub day: Option<u32>,
+    pub line: usize,
+    pub column: usize,
+    /// The whole offending line, empty when the input ended too early.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: impl Into<String>) -> ParseError {
+        ParseError {
+            day: None,
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// The input ended while `message` was still expected.
+    pub fn end_of_input(input: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(input.lines().count() + 1, 1, "", message)
+    }
+
+    pub fn in_day(self, day: u32) -> ParseError {
+        ParseError {
+            day: Some(day),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if !self.text.is_empty() {
+            write!(f, "\n    {}\n    {:>2$}", self.text, "^", self.column)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An error within a single line, it becomes a `ParseError` once the line number is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    pub column: usize,
+    pub message: String,
+}
+
+impl LineError {
+    pub fn new(column: usize, message: impl Into<String>) -> LineError {
+        LineError {
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// An error located at the start of `part`, which is a slice of `line`.
+    pub fn at(line: &str, part: &str, message: impl Into<String>) -> LineError {
+        LineError::new(column_of(line, part), message)
+    }
+
+    pub fn on_line(self, number: usize, line: &str) -> ParseError {
+        ParseError::new(number, self.column, line, self.message)
+    }
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl std::error::Error for LineError {}
+
+/// The column where `part` starts, provided `part` is a slice of `line`, otherwise 1.
+pub fn column_of(line: &str, part: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let offset = (part.as_ptr() as usize).wrapping_sub(start);
+    if offset <= line.len() {
+        line[..offset].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+/// Parses every line of the input, errors of `parse_line` are located on their line.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut parse_line: impl FnMut(&'a str) -> Result<T, LineError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(line).map_err(|e| e.on_line(i + 1, line)))
+        .collect()
+}
+
+/// Parses `part` of `line` as a number.
+pub fn number<T: FromStr>(line: &str, part: &str) -> Result<T, LineError> {
+    part.parse::<T>()
+        .map_err(|_| LineError::at(line, part, format!("expected a number, found '{part}'")))
+}
+
+/// Splits `part` of `line` around the first `delimiter`.
+pub fn split_once<'a>(
+    line: &str,
+    part: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), LineError> {
+    part.split_once(delimiter)
+        .ok_or_else(|| LineError::at(line, part, format!("expected '{delimiter}'")))
+}
+
+/// Removes `prefix` from `part` of `line`.
+pub fn strip_prefix<'a>(line: &str, part: &'a str, prefix: &str) -> Result<&'a str, LineError> {
+    part.strip_prefix(prefix)
+        .ok_or_else(|| LineError::at(line, part, format!("expected '{prefix}'")))
+}
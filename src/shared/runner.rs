@@ -0,0 +1,164 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use super::cli::{Cli, Options};
+use super::{read_stdin, ParseError, Solver};
+use crate::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "a" | "A" => Ok(Part::A),
+            "b" | "B" => Ok(Part::B),
+            _ => Err(format!("Unknown part '{s}', expected 'a' or 'b'")),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::A => write!(f, "a"),
+            Part::B => write!(f, "b"),
+        }
+    }
+}
+
+/// Parses the input and solves one part of a day's puzzle, rendering the answer. Fails with the
+/// `ParseError` or with why solving failed.
+pub type Solution = fn(Part, &str) -> Result<Option<String>, Box<dyn Error>>;
+
+pub fn solve<S: Solver>(part: Part, input: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let parsed = S::parse(input).map_err(|e| e.in_day(S::DAY))?;
+    let answer = match part {
+        Part::A => S::try_part_a(&parsed)?.map(|answer| answer.to_string()),
+        Part::B => S::try_part_b(&parsed)?.map(|answer| answer.to_string()),
+    };
+
+    Ok(answer)
+}
+
+pub struct Day {
+    pub number: u32,
+    pub solve: Solution,
+}
+
+macro_rules! day {
+    ($solver:ty) => {
+        Day {
+            number: <$solver>::DAY,
+            solve: solve::<$solver>,
+        }
+    };
+}
+
+/// Every day of the calendar in order, unsolved parts answer `None`.
+pub const DAYS: &[Day] = &[
+    day!(day01::Day01),
+    day!(day02::Day02),
+    day!(day03::Day03),
+    day!(day04::Day04),
+    day!(day05::Day05),
+    day!(day06::Day06),
+    day!(day07::Day07),
+    day!(day08::Day08),
+    day!(day09::Day09),
+    day!(day10::Day10),
+    day!(day11::Day11),
+    day!(day12::Day12),
+    day!(day13::Day13),
+    day!(day14::Day14),
+    day!(day15::Day15),
+    day!(day16::Day16),
+    day!(day17::Day17),
+    day!(day18::Day18),
+    day!(day19::Day19),
+    day!(day20::Day20),
+    day!(day21::Day21),
+    day!(day22::Day22),
+    day!(day23::Day23),
+    day!(day24::Day24),
+    day!(day25::Day25),
+];
+
+pub fn find_day(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+/// Runs a solution and measures the wall-clock time it took, parsing included.
+pub fn run_timed(
+    solution: Solution,
+    part: Part,
+    input: &str,
+) -> (Result<Option<String>, Box<dyn Error>>, Duration) {
+    let start = Instant::now();
+    let answer = solution(part, input);
+    (answer, start.elapsed())
+}
+
+/// Entry point of the per-day binaries: solves one part for the input on stdin.
+pub fn run_stdin<S: Solver>(part: Part) -> ExitCode {
+    match solve::<S>(part, &read_stdin()) {
+        Ok(Some(answer)) => {
+            println!("{answer}");
+            ExitCode::SUCCESS
+        }
+        Ok(None) => {
+            eprintln!("The input has no answer");
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parses the input on stdin, for binaries that do more with it than printing an answer.
+pub fn parse_stdin<S: Solver>() -> Result<S::Input, ParseError> {
+    S::parse(&read_stdin()).map_err(|e| e.in_day(S::DAY))
+}
+
+/// Reads and parses the whole input, for binaries given their input as a stream.
+pub fn parse_input<S: Solver>(input: &mut dyn Read) -> Result<S::Input, Box<dyn Error>> {
+    let text = io::read_to_string(input)?;
+    Ok(S::parse(&text).map_err(|e| e.in_day(S::DAY))?)
+}
+
+/// Entry point of the per-day binaries that take options. Without options the part is solved
+/// like `run_stdin` does, otherwise `cli` handles them.
+pub fn run_cli<S: Solver>(part: Part, cli: &Cli) -> ExitCode {
+    match Options::from_env(cli.flags, cli.valued) {
+        Ok(options) if options.is_empty() => run_stdin::<S>(part),
+        Ok(options) => exit_code((cli.run)(
+            part,
+            &options,
+            &mut io::stdin().lock(),
+            &mut io::stdout().lock(),
+        )),
+        Err(e) => exit_code(Err(e.into())),
+    }
+}
+
+/// Exit code of a binary, the error is reported on stderr.
+pub fn exit_code(result: Result<(), Box<dyn Error>>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use priority_queue::PriorityQueue;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// A cheapest way to a goal, `nodes` runs from one of the starts to the goal, both included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: u32,
+    pub nodes: Vec<N>,
+}
+
+impl<N> Path<N> {
+    pub fn start(&self) -> &N {
+        self.nodes.first().expect("Path has at least one node")
+    }
+
+    pub fn goal(&self) -> &N {
+        self.nodes.last().expect("Path has at least one node")
+    }
+}
+
+fn reconstruct<N: Clone>(goal: N, parent: impl Fn(&N) -> Option<N>) -> Vec<N> {
+    let mut nodes = vec![goal];
+    while let Some(p) = parent(nodes.last().expect("Nodes are not empty")) {
+        nodes.push(p);
+    }
+    nodes.reverse();
+
+    nodes
+}
+
+/// Breadth-first search where every step costs 1. Starts from all of `starts` at once and
+/// returns a shortest path to the first node satisfying `is_goal`.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::<N, Option<N>>::new();
+    let mut queue = VecDeque::<(N, u32)>::new();
+
+    for start in starts {
+        if !parents.contains_key(&start) {
+            parents.insert(start.clone(), None);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, cost)) = queue.pop_front() {
+        if is_goal(&node) {
+            let nodes = reconstruct(node, |n| parents[n].clone());
+            return Some(Path { cost, nodes });
+        }
+
+        for next in neighbours(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Every node reachable from `starts`, the starts included.
+pub fn reachable<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::<N>::new();
+    let mut queue = VecDeque::<N>::new();
+
+    for start in starts {
+        if seen.insert(start.clone()) {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if seen.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    seen
+}
+
+/// Dijkstra's algorithm, `neighbours` yields the nodes one step away along with the cost of the
+/// step.
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, u32)>,
+{
+    astar(starts, neighbours, |_| 0, is_goal)
+}
+
+/// A* search, `heuristic` must never overestimate the remaining cost to a goal and must not
+/// drop by more than the cost of a step, otherwise the path found may not be the cheapest.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> u32,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, u32)>,
+{
+    // Cheapest known cost of every discovered node and the node it was reached from
+    let mut best = HashMap::<N, (u32, Option<N>)>::new();
+    let mut done = HashSet::<N>::new();
+    let mut q = PriorityQueue::<N, Reverse<u32>>::new();
+
+    for start in starts {
+        let priority = Reverse(heuristic(&start));
+        best.insert(start.clone(), (0, None));
+        q.push(start, priority);
+    }
+
+    while let Some((node, _)) = q.pop() {
+        let cost = best[&node].0;
+        if is_goal(&node) {
+            let nodes = reconstruct(node, |n| best[n].1.clone());
+            return Some(Path { cost, nodes });
+        }
+
+        for (next, step) in neighbours(&node) {
+            if done.contains(&next) {
+                continue;
+            }
+
+            let next_cost = cost + step;
+            if best.get(&next).is_none_or(|&(known, _)| next_cost < known) {
+                let priority = Reverse(next_cost + heuristic(&next));
+                best.insert(next.clone(), (next_cost, Some(node.clone())));
+                q.push_increase(next, priority);
+            }
+        }
+
+        done.insert(node);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Edges of a small weighted graph: the direct way from 'a' to 'e' is the cheapest only when
+    /// counting steps.
+    const EDGES: [(char, char, u32); 7] = [
+        ('a', 'e', 10),
+        ('a', 'b', 1),
+        ('b', 'c', 1),
+        ('c', 'd', 1),
+        ('d', 'e', 1),
+        ('f', 'e', 3),
+        ('g', 'a', 1),
+    ];
+
+    fn weighted(node: &char) -> Vec<(char, u32)> {
+        EDGES
+            .iter()
+            .filter(|&&(from, _, _)| from == *node)
+            .map(|&(_, to, cost)| (to, cost))
+            .collect()
+    }
+
+    fn unweighted(node: &char) -> Vec<char> {
+        weighted(node).into_iter().map(|(to, _)| to).collect()
+    }
+
+    /// Steps between free cells of a maze, '#' are walls.
+    fn maze_steps(maze: &[&str], (x, y): (usize, usize)) -> Vec<((usize, usize), u32)> {
+        let free = |x: usize, y: usize| {
+            maze.get(y)
+                .and_then(|row| row.as_bytes().get(x))
+                .is_some_and(|&c| c != b'#')
+        };
+
+        [
+            (x + 1, y),
+            (x.wrapping_sub(1), y),
+            (x, y + 1),
+            (x, y.wrapping_sub(1)),
+        ]
+        .into_iter()
+        .filter(|&(x, y)| free(x, y))
+        .map(|next| (next, 1))
+        .collect()
+    }
+
+    const MAZE: [&str; 5] = ["......", ".####.", ".#..#.", ".#.##.", "...#.."];
+
+    #[test]
+    fn bfs_counts_steps() {
+        let path = bfs(['a'], unweighted, |&n| n == 'e').unwrap();
+
+        assert_eq!(path.cost, 1);
+        assert_eq!(path.nodes, vec!['a', 'e']);
+    }
+
+    #[test]
+    fn bfs_starts_from_the_closest_start() {
+        let line = |&n: &u32| [n + 1];
+        let path = bfs([0, 8, 3], line, |&n| n == 10).unwrap();
+
+        assert_eq!(path.cost, 2);
+        assert_eq!(path.nodes, vec![8, 9, 10]);
+        assert_eq!((*path.start(), *path.goal()), (8, 10));
+    }
+
+    #[test]
+    fn bfs_reconstructs_the_path_through_a_maze() {
+        let neighbours = |&p: &(usize, usize)| maze_steps(&MAZE, p).into_iter().map(|(n, _)| n);
+        let path = bfs([(2, 2)], neighbours, |&p| p == (5, 4)).unwrap();
+
+        assert_eq!(path.cost, 17);
+        assert_eq!(path.nodes.len(), 18);
+        assert_eq!(path.nodes[..4], [(2, 2), (2, 3), (2, 4), (1, 4)]);
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|w| maze_steps(&MAZE, w[0]).iter().any(|&(n, _)| n == w[1])));
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheap_over_short() {
+        let path = dijkstra(['a'], weighted, |&n| n == 'e').unwrap();
+
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.nodes, vec!['a', 'b', 'c', 'd', 'e']);
+    }
+
+    #[test]
+    fn dijkstra_starts_from_the_cheapest_start() {
+        let path = dijkstra(['g', 'f', 'c'], weighted, |&n| n == 'e').unwrap();
+
+        assert_eq!(path.cost, 2);
+        assert_eq!(*path.start(), 'c');
+        assert_eq!(path.nodes, vec!['c', 'd', 'e']);
+    }
+
+    #[test]
+    fn astar_agrees_with_bfs_in_a_maze() {
+        let goal = (5, 4);
+        let manhattan = |&(x, y): &(usize, usize)| (x.abs_diff(goal.0) + y.abs_diff(goal.1)) as u32;
+        let path = astar(
+            [(2, 2)],
+            |&p| maze_steps(&MAZE, p),
+            manhattan,
+            |&p| p == goal,
+        )
+        .unwrap();
+
+        assert_eq!(path.cost, 17);
+        assert_eq!(*path.goal(), goal);
+
+        let weighted_path = astar(['a'], weighted, |_| 0, |&n| n == 'e').unwrap();
+        assert_eq!(weighted_path.nodes, vec!['a', 'b', 'c', 'd', 'e']);
+    }
+
+    #[test]
+    fn unreachable_goals_have_no_path() {
+        assert_eq!(bfs(['e'], unweighted, |&n| n == 'a'), None);
+        assert_eq!(dijkstra(['e'], weighted, |&n| n == 'a'), None);
+        assert_eq!(astar(['b'], weighted, |_| 0, |&n| n == 'f'), None);
+        assert_eq!(bfs(Vec::<char>::new(), unweighted, |_| true), None);
+    }
+
+    #[test]
+    fn a_start_can_be_the_goal() {
+        let here = Path {
+            cost: 0,
+            nodes: vec!['c'],
+        };
+
+        assert_eq!(
+            bfs(['a', 'c'], unweighted, |&n| n == 'c'),
+            Some(here.clone())
+        );
+        assert_eq!(
+            dijkstra(['a', 'c'], weighted, |&n| n == 'c'),
+            Some(here.clone())
+        );
+        assert_eq!(astar(['c'], weighted, |_| 5, |&n| n == 'c'), Some(here));
+    }
+
+    #[test]
+    fn reachable_includes_the_starts() {
+        let nodes = reachable(['c', 'f'], unweighted);
+
+        assert_eq!(nodes, HashSet::from(['c', 'd', 'e', 'f']));
+    }
+}